@@ -0,0 +1,185 @@
+use super::{
+    shortest_path::{floyd_warshal, ShortestPath},
+    traversal::Traversal,
+    weight::{Dist, Weight},
+    Graph,
+};
+use ndarray::{parallel::prelude::*, Array2, Axis};
+
+/// Graphs up to this size may be solved by `floyd_warshal` instead of one search per source.
+const FLOYD_WARSHALL_MAX_VERTICES: usize = 256;
+
+pub trait AllPairsShortestPaths<W: Weight> {
+    /// `dist[[src, dst]]` for every pair of vertices, `W::Dist::INFINITY` when `dst` is
+    /// unreachable.
+    /// Small dense graphs go through `floyd_warshal`, the others through
+    /// `parallel_all_pairs_distances`.
+    fn all_pairs_distances(&self) -> Array2<Dist<W>>;
+    /// One search per source, spread across threads: a breadth-first search when every edge has
+    /// the same weight, Dijkstra's algorithm otherwise.
+    fn parallel_all_pairs_distances(&self) -> Array2<Dist<W>>;
+}
+
+impl<G> AllPairsShortestPaths<G::Weight> for G
+where
+    G: Graph + Sync,
+    G::Weight: Send + Sync,
+    Dist<G::Weight>: Send + Sync,
+{
+    fn all_pairs_distances(&self) -> Array2<Dist<G::Weight>> {
+        match strategy(self) {
+            Strategy::FloydWarshall => {
+                let nb_vertices = self.nb_vertices();
+                let mut dist =
+                    Array2::from_elem((nb_vertices, nb_vertices), Dist::<G::Weight>::INFINITY);
+                for edge in self.edges() {
+                    let weight = &mut dist[[edge.src, edge.dst]];
+                    *weight = (*weight).min(edge.weight.into());
+                }
+                dist.diag_mut().fill(Dist::<G::Weight>::ZERO);
+                floyd_warshal(&mut dist);
+                dist
+            }
+            Strategy::Bfs(weight) => per_source_distances(self, Some(weight)),
+            Strategy::Dijkstra => per_source_distances(self, None),
+        }
+    }
+
+    fn parallel_all_pairs_distances(&self) -> Array2<Dist<G::Weight>> {
+        per_source_distances(self, uniform_weight(self))
+    }
+}
+
+/// How `all_pairs_distances` solves a graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Strategy<W> {
+    FloydWarshall,
+    /// Breadth-first searches, every edge weighing the same
+    Bfs(W),
+    Dijkstra,
+}
+
+fn strategy<G: Graph>(graph: &G) -> Strategy<G::Weight> {
+    let nb_vertices = graph.nb_vertices();
+    let dense = graph.nb_edges() * 4 >= nb_vertices * nb_vertices;
+    if nb_vertices <= FLOYD_WARSHALL_MAX_VERTICES && dense {
+        return Strategy::FloydWarshall;
+    }
+    match uniform_weight(graph) {
+        Some(weight) => Strategy::Bfs(weight),
+        None => Strategy::Dijkstra,
+    }
+}
+
+/// Weight shared by every edge, `None` when they differ or there is no edge.
+fn uniform_weight<G: Graph>(graph: &G) -> Option<G::Weight> {
+    let mut weights = graph.edges().map(|edge| edge.weight);
+    weights.next().filter(|&first| weights.all(|w| w == first))
+}
+
+/// `parallel_all_pairs_distances`, breadth-first searches only when `uniform_weight` is known.
+fn per_source_distances<G>(graph: &G, uniform_weight: Option<G::Weight>) -> Array2<Dist<G::Weight>>
+where
+    G: Graph + Sync,
+    G::Weight: Send + Sync,
+    Dist<G::Weight>: Send + Sync,
+{
+    let nb_vertices = graph.nb_vertices();
+    let mut dist = Array2::from_elem((nb_vertices, nb_vertices), Dist::<G::Weight>::INFINITY);
+    dist.axis_iter_mut(Axis(0))
+        .into_par_iter()
+        .enumerate()
+        .for_each(|(src, mut row)| match uniform_weight {
+            Some(weight) => {
+                let mut layer_dist = Dist::<G::Weight>::ZERO;
+                for layer in graph.bfs_layers(src) {
+                    for vertex_id in layer {
+                        row[vertex_id] = layer_dist;
+                    }
+                    layer_dist = layer_dist.saturating_add(weight.into());
+                }
+            }
+            None => {
+                for (vertex_id, d) in graph.disjktra_distances(src).into_iter().enumerate() {
+                    row[vertex_id] = d;
+                }
+            }
+        });
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{dgraph::Dgraph, shortest_path::floyd_warshal};
+
+    /// `width`x`width` grid with edges going right, costing 1, and going down.
+    fn make_grid(width: usize, down_weight: isize) -> Dgraph {
+        let mut edges = Vec::new();
+        for id in 0..width * width {
+            if id % width < width - 1 {
+                edges.push((id, id + 1, 1));
+            }
+            if id + width < width * width {
+                edges.push((id, id + width, down_weight));
+            }
+        }
+        Dgraph::from_edges(width * width, &edges).unwrap()
+    }
+
+    #[test]
+    fn same_distances_as_floyd_warshall() {
+        let graph = make_grid(10, 3);
+        let mut expected = graph.to_distance_matrix();
+        floyd_warshal(&mut expected);
+        let distances = graph.all_pairs_distances();
+        assert_eq!(distances, expected);
+        assert_eq!(distances[[0, 99]], 36);
+
+        // Unit weights, going through breadth-first searches
+        let distances = make_grid(10, 1).parallel_all_pairs_distances();
+        assert_eq!(distances[[0, 99]], 18);
+        assert_eq!(distances[[99, 0]], isize::MAX);
+    }
+
+    #[test]
+    fn dense_graph_goes_through_floyd_warshall() {
+        // Complete graph on 5 vertices, hopping one vertex at a time beats the direct edge
+        let edges: Vec<(usize, usize, isize)> = (0..5)
+            .flat_map(|src| (0..5).map(move |dst| (src, dst)))
+            .filter(|(src, dst)| src != dst)
+            .map(|(src, dst)| (src, dst, (src as isize - dst as isize).pow(2)))
+            .collect();
+        let graph = Dgraph::from_edges(5, &edges).unwrap();
+        assert_eq!(strategy(&graph), Strategy::FloydWarshall);
+        let distances = graph.all_pairs_distances();
+        assert_eq!(distances, graph.parallel_all_pairs_distances());
+        assert_eq!(distances[[0, 4]], 4);
+        assert_eq!(distances[[3, 1]], 2);
+    }
+
+    /// `nb_vertices` vertices on a ring, each linked to the `nb_next` following ones.
+    fn make_ring(nb_vertices: usize, nb_next: usize) -> Dgraph {
+        let edges: Vec<(usize, usize, isize)> = (0..nb_vertices)
+            .flat_map(|src| (1..=nb_next).map(move |step| (src, (src + step) % nb_vertices, 1)))
+            .collect();
+        Dgraph::from_edges(nb_vertices, &edges).unwrap()
+    }
+
+    #[test]
+    fn choose_strategy() {
+        assert_eq!(strategy(&make_grid(10, 3)), Strategy::Dijkstra);
+        assert_eq!(strategy(&make_grid(10, 1)), Strategy::Bfs(1));
+
+        // 256 * 64 edges are exactly dense enough, one less is not
+        let mut graph = make_ring(FLOYD_WARSHALL_MAX_VERTICES, 64);
+        assert_eq!(strategy(&graph), Strategy::FloydWarshall);
+        graph.remove_edge(0, 1);
+        assert_eq!(strategy(&graph), Strategy::Bfs(1));
+
+        // Dense, but one vertex too many
+        let graph = make_ring(FLOYD_WARSHALL_MAX_VERTICES + 1, 65);
+        assert!(graph.nb_edges() * 4 >= graph.nb_vertices().pow(2));
+        assert_eq!(strategy(&graph), Strategy::Bfs(1));
+    }
+}
@@ -0,0 +1,343 @@
+use super::{
+    csr::CsrGraph,
+    dgraph::Edge,
+    weight::{Dist, Weight},
+    Graph,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+/// Result of a bidirectional search: the vertex where both searches met and the stitched path
+/// from the source to the target, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Meeting<N, W = isize> {
+    pub vertex: N,
+    pub cost: W,
+    pub path: Vec<N>,
+}
+
+/// Bidirectional searches on explicit graphs. The backward search follows the edges in reverse,
+/// which are gathered up front for directed graphs.
+pub trait BidirectionalSearch<W: Weight> {
+    /// Fewest edges from `src` to `dst`, ignoring the weights.
+    fn bidirectional_bfs(&self, src: usize, dst: usize) -> Option<Meeting<usize, usize>>;
+    /// Cheapest path from `src` to `dst`, the weights must be positive.
+    fn bidirectional_disjktra(&self, src: usize, dst: usize) -> Option<Meeting<usize, Dist<W>>>;
+}
+
+impl<G: Graph> BidirectionalSearch<G::Weight> for G {
+    fn bidirectional_bfs(&self, src: usize, dst: usize) -> Option<Meeting<usize, usize>> {
+        let reversed = reverse_edges(self);
+        bidirectional_bfs(
+            src,
+            dst,
+            |&vertex_id| self.neighbors(vertex_id).iter().map(|edge| edge.dst),
+            |&vertex_id| {
+                match &reversed {
+                    Some(reversed) => reversed.neighbors(vertex_id),
+                    None => self.neighbors(vertex_id),
+                }
+                .iter()
+                .map(|edge| edge.dst)
+            },
+        )
+    }
+
+    fn bidirectional_disjktra(
+        &self,
+        src: usize,
+        dst: usize,
+    ) -> Option<Meeting<usize, Dist<G::Weight>>> {
+        let reversed = reverse_edges(self);
+        bidirectional_disjktra(
+            src,
+            dst,
+            |&vertex_id| {
+                self.neighbors(vertex_id)
+                    .iter()
+                    .map(|edge| (edge.dst, edge.weight))
+            },
+            |&vertex_id| {
+                match &reversed {
+                    Some(reversed) => reversed.neighbors(vertex_id),
+                    None => self.neighbors(vertex_id),
+                }
+                .iter()
+                .map(|edge| (edge.dst, edge.weight))
+            },
+        )
+    }
+}
+
+/// Incoming edges of every vertex, `None` when the graph is undirected and they are the
+/// outgoing ones.
+fn reverse_edges<G: Graph>(graph: &G) -> Option<CsrGraph<G::Weight>> {
+    graph.is_directed().then(|| {
+        CsrGraph::from_valid_edges(
+            graph.nb_vertices(),
+            graph
+                .edges()
+                .map(|edge| Edge {
+                    src: edge.dst,
+                    dst: edge.src,
+                    weight: edge.weight,
+                })
+                .collect(),
+        )
+    })
+}
+
+/// Breadth-first search from both ends of an implicit graph: `successors` lists the vertices
+/// reachable in one step and `predecessors` the ones reaching a vertex in one step. The
+/// smaller frontier is expanded one whole layer at a time.
+pub fn bidirectional_bfs<N, S, P, SI, PI>(
+    start: N,
+    goal: N,
+    mut successors: S,
+    mut predecessors: P,
+) -> Option<Meeting<N, usize>>
+where
+    N: Eq + Hash + Clone,
+    S: FnMut(&N) -> SI,
+    SI: IntoIterator<Item = N>,
+    P: FnMut(&N) -> PI,
+    PI: IntoIterator<Item = N>,
+{
+    if start == goal {
+        return Some(Meeting {
+            vertex: start.clone(),
+            cost: 0,
+            path: vec![start],
+        });
+    }
+    // Parent and distance of every visited vertex, for each direction
+    let mut forward = HashMap::from([(start.clone(), (None, 0))]);
+    let mut backward = HashMap::from([(goal.clone(), (None, 0))]);
+    let mut forward_frontier = vec![start];
+    let mut backward_frontier = vec![goal];
+
+    while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+        let expand_forward = forward_frontier.len() <= backward_frontier.len();
+        let (frontier, visited, other) = if expand_forward {
+            (&mut forward_frontier, &mut forward, &backward)
+        } else {
+            (&mut backward_frontier, &mut backward, &forward)
+        };
+
+        // Every meeting found in this layer is considered, the first one is not always the best
+        let mut meeting: Option<(N, usize)> = None;
+        let mut next_frontier = Vec::new();
+        for vertex in frontier.drain(..) {
+            let dist = visited[&vertex].1 + 1;
+            let next_vertices: Vec<N> = if expand_forward {
+                successors(&vertex).into_iter().collect()
+            } else {
+                predecessors(&vertex).into_iter().collect()
+            };
+            for next in next_vertices {
+                if visited.contains_key(&next) {
+                    continue;
+                }
+                visited.insert(next.clone(), (Some(vertex.clone()), dist));
+                if let Some(&(_, other_dist)) = other.get(&next) {
+                    if meeting
+                        .as_ref()
+                        .is_none_or(|(_, cost)| dist + other_dist < *cost)
+                    {
+                        meeting = Some((next.clone(), dist + other_dist));
+                    }
+                }
+                next_frontier.push(next);
+            }
+        }
+        *frontier = next_frontier;
+
+        if let Some((vertex, cost)) = meeting {
+            let path = stitch(&vertex, &forward, &backward);
+            return Some(Meeting { vertex, cost, path });
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm from both ends of an implicit graph, `successors` and `predecessors`
+/// yield the neighbors along with the (positive) weight of the edge, the cost is summed up in
+/// `W::Dist`. It stops once the two frontiers together cannot improve on the best path found.
+pub fn bidirectional_disjktra<N, W, S, P, SI, PI>(
+    start: N,
+    goal: N,
+    mut successors: S,
+    mut predecessors: P,
+) -> Option<Meeting<N, Dist<W>>>
+where
+    N: Ord + Hash + Clone,
+    W: Weight,
+    S: FnMut(&N) -> SI,
+    SI: IntoIterator<Item = (N, W)>,
+    P: FnMut(&N) -> PI,
+    PI: IntoIterator<Item = (N, W)>,
+{
+    let mut forward = HashMap::from([(start.clone(), (None, Dist::<W>::ZERO))]);
+    let mut backward = HashMap::from([(goal.clone(), (None, Dist::<W>::ZERO))]);
+    let mut forward_heap = BinaryHeap::from([Reverse((Dist::<W>::ZERO, start.clone()))]);
+    let mut backward_heap = BinaryHeap::from([Reverse((Dist::<W>::ZERO, goal.clone()))]);
+    let mut meeting = (start == goal).then(|| (start.clone(), Dist::<W>::ZERO));
+
+    while let (Some(&Reverse((forward_top, _))), Some(&Reverse((backward_top, _)))) =
+        (forward_heap.peek(), backward_heap.peek())
+    {
+        if meeting
+            .as_ref()
+            .is_some_and(|(_, cost)| forward_top.saturating_add(backward_top) >= *cost)
+        {
+            break;
+        }
+
+        let expand_forward = forward_top <= backward_top;
+        let (heap, visited, other) = if expand_forward {
+            (&mut forward_heap, &mut forward, &backward)
+        } else {
+            (&mut backward_heap, &mut backward, &forward)
+        };
+        let Reverse((cur_dist, vertex)) = heap.pop().unwrap();
+        if cur_dist > visited[&vertex].1 {
+            // Stale entry, the vertex was reached through a shorter path since
+            continue;
+        }
+        if cur_dist == Dist::<W>::INFINITY {
+            // Saturated distance, the vertex is not actually reachable
+            continue;
+        }
+        let next_vertices: Vec<(N, W)> = if expand_forward {
+            successors(&vertex).into_iter().collect()
+        } else {
+            predecessors(&vertex).into_iter().collect()
+        };
+        for (next, weight) in next_vertices {
+            let new_dist = cur_dist.saturating_add(weight.into());
+            if visited
+                .get(&next)
+                .is_some_and(|&(_, dist)| dist <= new_dist)
+            {
+                continue;
+            }
+            visited.insert(next.clone(), (Some(vertex.clone()), new_dist));
+            if let Some(&(_, other_dist)) = other.get(&next) {
+                let cost = new_dist.saturating_add(other_dist);
+                if cost != Dist::<W>::INFINITY
+                    && meeting.as_ref().is_none_or(|(_, best)| cost < *best)
+                {
+                    meeting = Some((next.clone(), cost));
+                }
+            }
+            heap.push(Reverse((new_dist, next)));
+        }
+    }
+
+    meeting.map(|(vertex, cost)| {
+        let path = stitch(&vertex, &forward, &backward);
+        Meeting { vertex, cost, path }
+    })
+}
+
+type Parents<N, W> = HashMap<N, (Option<N>, W)>;
+
+/// Follows the forward parents back to the start, then the backward parents to the goal.
+fn stitch<N: Eq + Hash + Clone, W>(
+    meeting: &N,
+    forward: &Parents<N, W>,
+    backward: &Parents<N, W>,
+) -> Vec<N> {
+    let mut path = vec![meeting.clone()];
+    while let Some((Some(parent), _)) = forward.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    let mut vertex = meeting;
+    while let Some((Some(parent), _)) = backward.get(vertex) {
+        path.push(parent.clone());
+        vertex = parent;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{dgraph::Dgraph, shortest_path::ShortestPath};
+
+    #[test]
+    fn explicit_graph() {
+        let graph: Dgraph = Dgraph::from_edges(
+            6,
+            &[
+                (0, 1, 7),
+                (0, 2, 9),
+                (0, 5, 14),
+                (1, 2, 10),
+                (1, 3, 15),
+                (2, 3, 11),
+                (2, 5, 2),
+                (3, 4, 6),
+                (5, 4, 9),
+            ],
+        )
+        .unwrap();
+        let meeting = graph.bidirectional_disjktra(0, 4).unwrap();
+        assert_eq!(meeting.cost, 20);
+        assert_eq!(meeting.path, vec![0, 2, 5, 4]);
+        assert!(meeting.path.contains(&meeting.vertex));
+        assert_eq!(Some(meeting.path), graph.disjktra_path(0, 4));
+
+        let meeting = graph.bidirectional_bfs(0, 4).unwrap();
+        assert_eq!(meeting.cost, 2);
+        assert_eq!(meeting.path, vec![0, 5, 4]);
+        assert_eq!(graph.bidirectional_bfs(4, 0), None);
+        assert_eq!(graph.bidirectional_disjktra(3, 3).unwrap().path, vec![3]);
+        assert_eq!(graph.bidirectional_disjktra(4, 0), None);
+
+        // A path saturating the distance type is as unreachable as no path at all
+        let heavy = isize::MAX / 2 + 1;
+        let graph: Dgraph = Dgraph::from_edges(3, &[(0, 1, heavy), (1, 2, heavy)]).unwrap();
+        assert_eq!(graph.disjktra_path(0, 2), None);
+        assert_eq!(graph.bidirectional_disjktra(0, 2), None);
+
+        let graph: Dgraph<u8> = Dgraph::from_edges(3, &[(0, 1, u8::MAX), (1, 2, 1)]).unwrap();
+        let meeting = graph.bidirectional_disjktra(0, 2).unwrap();
+        assert_eq!(meeting.cost, 256);
+        assert_eq!(meeting.path, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn implicit_graph() {
+        // Reach 100 from 1 with the moves `+1` and `*2`
+        let successors = |&n: &u32| [n + 1, n * 2];
+        let predecessors = |&n: &u32| {
+            let mut prev = vec![n - 1];
+            if n % 2 == 0 {
+                prev.push(n / 2);
+            }
+            prev.into_iter().filter(|&p| p >= 1)
+        };
+        let meeting = bidirectional_bfs(1, 100, successors, predecessors).unwrap();
+        // 1 2 3 6 12 24 25 50 100
+        assert_eq!(meeting.cost, 8);
+        assert_eq!(meeting.path.len(), 9);
+        assert!(meeting
+            .path
+            .windows(2)
+            .all(|pair| pair[1] == pair[0] + 1 || pair[1] == pair[0] * 2));
+
+        let weighted = bidirectional_disjktra(
+            1,
+            100,
+            |n| successors(n).map(|next| (next, 1_usize)),
+            |n| predecessors(n).map(|prev| (prev, 1)),
+        )
+        .unwrap();
+        assert_eq!(weighted.cost, 8);
+    }
+}
@@ -0,0 +1,324 @@
+use super::{
+    dgraph::{Dgraph, Edge},
+    Graph,
+};
+use std::collections::{HashMap, VecDeque};
+
+/// Vertices of a directed cycle, in order. The cycle closes with an edge from the last vertex
+/// back to the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<usize>);
+
+/// Graph of the strongly connected components: vertex `i` of `graph` is `components[i]`.
+#[derive(Debug, Clone)]
+pub struct Condensation<W = isize> {
+    pub graph: Dgraph<W>,
+    pub components: Vec<Vec<usize>>,
+    pub component_of: Vec<usize>,
+}
+
+pub trait Connectivity<W> {
+    /// Kahn's algorithm. When the graph is not a DAG, one of its cycles is returned instead.
+    fn topological_sort(&self) -> Result<Vec<usize>, Cycle>;
+    /// Strongly connected components, listed in topological order of the condensation.
+    fn tarjan_scc(&self) -> Vec<Vec<usize>>;
+    /// Same result as `tarjan_scc`, computed with two passes of depth-first search.
+    fn kosaraju_scc(&self) -> Vec<Vec<usize>>;
+    /// Components when edge directions are ignored, ordered by their smallest vertex.
+    fn weakly_connected_components(&self) -> Vec<Vec<usize>>;
+    /// DAG of the strongly connected components. Parallel edges between two components are
+    /// merged and keep the lightest weight.
+    fn condensation(&self) -> Condensation<W>;
+}
+
+impl<G: Graph> Connectivity<G::Weight> for G {
+    fn topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        let nb_vertices = self.nb_vertices();
+        let mut in_degree = vec![0; nb_vertices];
+        for edge in self.edges() {
+            in_degree[edge.dst] += 1;
+        }
+        let mut queue: VecDeque<usize> = (0..nb_vertices).filter(|&v| in_degree[v] == 0).collect();
+        let mut order = Vec::with_capacity(nb_vertices);
+        while let Some(vertex_id) = queue.pop_front() {
+            order.push(vertex_id);
+            for neighbor in self.neighbors(vertex_id) {
+                in_degree[neighbor.dst] -= 1;
+                if in_degree[neighbor.dst] == 0 {
+                    queue.push_back(neighbor.dst);
+                }
+            }
+        }
+        if order.len() == nb_vertices {
+            Ok(order)
+        } else {
+            Err(find_cycle(self, &in_degree))
+        }
+    }
+
+    fn tarjan_scc(&self) -> Vec<Vec<usize>> {
+        let nb_vertices = self.nb_vertices();
+        let mut index = vec![usize::MAX; nb_vertices];
+        let mut low_link = vec![0; nb_vertices];
+        let mut on_stack = vec![false; nb_vertices];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+
+        for root in 0..nb_vertices {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            // (vertex, position of the next neighbor to explore)
+            let mut call_stack = vec![(root, 0)];
+            index[root] = next_index;
+            low_link[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (vertex_id, ref mut next_neighbor)) = call_stack.last_mut() {
+                if let Some(edge) = self.neighbors(vertex_id).get(*next_neighbor) {
+                    *next_neighbor += 1;
+                    let dst = edge.dst;
+                    if index[dst] == usize::MAX {
+                        index[dst] = next_index;
+                        low_link[dst] = next_index;
+                        next_index += 1;
+                        stack.push(dst);
+                        on_stack[dst] = true;
+                        call_stack.push((dst, 0));
+                    } else if on_stack[dst] {
+                        low_link[vertex_id] = low_link[vertex_id].min(index[dst]);
+                    }
+                    continue;
+                }
+                call_stack.pop();
+                if let Some(&(parent_id, _)) = call_stack.last() {
+                    low_link[parent_id] = low_link[parent_id].min(low_link[vertex_id]);
+                }
+                if low_link[vertex_id] == index[vertex_id] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == vertex_id {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+        // Tarjan finds sink components first
+        components.reverse();
+        components
+    }
+
+    fn kosaraju_scc(&self) -> Vec<Vec<usize>> {
+        let nb_vertices = self.nb_vertices();
+
+        // First pass: order vertices by decreasing finish time
+        let mut visited = vec![false; nb_vertices];
+        let mut finish_order = Vec::with_capacity(nb_vertices);
+        for root in 0..nb_vertices {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut call_stack = vec![(root, 0)];
+            while let Some(&mut (vertex_id, ref mut next_neighbor)) = call_stack.last_mut() {
+                if let Some(edge) = self.neighbors(vertex_id).get(*next_neighbor) {
+                    *next_neighbor += 1;
+                    if !visited[edge.dst] {
+                        visited[edge.dst] = true;
+                        call_stack.push((edge.dst, 0));
+                    }
+                } else {
+                    finish_order.push(vertex_id);
+                    call_stack.pop();
+                }
+            }
+        }
+
+        // Second pass on the transposed graph
+        let mut reversed_neighbors = vec![Vec::new(); nb_vertices];
+        for edge in self.edges() {
+            reversed_neighbors[edge.dst].push(edge.src);
+        }
+        let mut assigned = vec![false; nb_vertices];
+        let mut components = Vec::new();
+        for &root in finish_order.iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+            assigned[root] = true;
+            let mut component = Vec::new();
+            let mut stack = vec![root];
+            while let Some(vertex_id) = stack.pop() {
+                component.push(vertex_id);
+                for &src in reversed_neighbors[vertex_id].iter() {
+                    if !assigned[src] {
+                        assigned[src] = true;
+                        stack.push(src);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    fn weakly_connected_components(&self) -> Vec<Vec<usize>> {
+        let nb_vertices = self.nb_vertices();
+        let mut undirected_neighbors = vec![Vec::new(); nb_vertices];
+        for edge in self.edges() {
+            undirected_neighbors[edge.src].push(edge.dst);
+            undirected_neighbors[edge.dst].push(edge.src);
+        }
+        let mut visited = vec![false; nb_vertices];
+        let mut components = Vec::new();
+        for root in 0..nb_vertices {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut component = Vec::new();
+            let mut stack = vec![root];
+            while let Some(vertex_id) = stack.pop() {
+                component.push(vertex_id);
+                for &neighbor in undirected_neighbors[vertex_id].iter() {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    fn condensation(&self) -> Condensation<G::Weight> {
+        let components = self.tarjan_scc();
+        let mut component_of = vec![0; self.nb_vertices()];
+        for (component_id, component) in components.iter().enumerate() {
+            for &vertex_id in component.iter() {
+                component_of[vertex_id] = component_id;
+            }
+        }
+
+        let mut lightest_edges: HashMap<(usize, usize), G::Weight> = HashMap::new();
+        for edge in self.edges() {
+            let (src, dst) = (component_of[edge.src], component_of[edge.dst]);
+            if src != dst {
+                let weight = lightest_edges.entry((src, dst)).or_insert(edge.weight);
+                *weight = (*weight).min(edge.weight);
+            }
+        }
+        let mut edges: Vec<Edge<G::Weight>> = lightest_edges
+            .into_iter()
+            .map(|((src, dst), weight)| Edge { src, dst, weight })
+            .collect();
+        edges.sort_unstable_by_key(|edge| (edge.src, edge.dst));
+
+        let mut graph = Dgraph::new(components.len());
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+        Condensation {
+            graph,
+            components,
+            component_of,
+        }
+    }
+}
+
+/// Walks backward from a vertex left over by Kahn's algorithm. Every leftover vertex has a
+/// leftover predecessor, so the walk must eventually run into a vertex it has already seen.
+fn find_cycle(graph: &impl Graph, in_degree: &[usize]) -> Cycle {
+    let mut predecessor = vec![None; graph.nb_vertices()];
+    for edge in graph.edges() {
+        if in_degree[edge.src] > 0 && in_degree[edge.dst] > 0 {
+            predecessor[edge.dst] = Some(edge.src);
+        }
+    }
+    let start = (0..graph.nb_vertices())
+        .find(|&v| in_degree[v] > 0)
+        .unwrap();
+    let mut position_in_walk = HashMap::new();
+    let mut walk = Vec::new();
+    let mut vertex_id = start;
+    while !position_in_walk.contains_key(&vertex_id) {
+        position_in_walk.insert(vertex_id, walk.len());
+        walk.push(vertex_id);
+        vertex_id = predecessor[vertex_id].unwrap();
+    }
+    let mut cycle = walk.split_off(position_in_walk[&vertex_id]);
+    // The walk followed edges backward
+    cycle.reverse();
+    Cycle(cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topological_sort() {
+        let graph: Dgraph =
+            Dgraph::from_edges(4, &[(0, 1, 1), (0, 2, 1), (2, 1, 1), (1, 3, 1)]).unwrap();
+        assert_eq!(graph.topological_sort(), Ok(vec![0, 2, 1, 3]));
+
+        let graph: Dgraph =
+            Dgraph::from_edges(4, &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 1, 1)]).unwrap();
+        let Cycle(cycle) = graph.topological_sort().unwrap_err();
+        assert_eq!(cycle.len(), 3);
+        for (i, &vertex_id) in cycle.iter().enumerate() {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(graph.vertices[vertex_id]
+                .neighbors
+                .iter()
+                .any(|e| e.dst == next));
+        }
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let graph: Dgraph = Dgraph::from_edges(
+            6,
+            &[
+                (0, 1, 1),
+                (1, 2, 1),
+                (2, 0, 1),
+                (2, 3, 1),
+                (3, 4, 1),
+                (4, 3, 1),
+                (5, 4, 1),
+            ],
+        )
+        .unwrap();
+        let expected = [vec![5], vec![0, 1, 2], vec![3, 4]];
+        let tarjan = graph.tarjan_scc();
+        let kosaraju = graph.kosaraju_scc();
+        for components in [tarjan, kosaraju] {
+            let mut sorted = components.clone();
+            sorted.sort();
+            assert_eq!(sorted, vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+            assert_eq!(components.last(), expected.last());
+        }
+
+        let condensation = graph.condensation();
+        assert_eq!(condensation.graph.vertices.len(), 3);
+        assert_eq!(condensation.graph.edges.len(), 2);
+        assert!(condensation.graph.topological_sort().is_ok());
+        assert_eq!(condensation.component_of[0], condensation.component_of[2]);
+        assert_eq!(
+            graph.weakly_connected_components(),
+            vec![vec![0, 1, 2, 3, 4, 5]]
+        );
+    }
+}
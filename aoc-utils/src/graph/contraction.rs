@@ -0,0 +1,78 @@
+use super::{
+    dgraph::{Dgraph, Edge},
+    shortest_path::ShortestPath,
+    weight::{Dist, Weight},
+    Graph,
+};
+
+/// Graph reduced to a subset of the vertices. Vertex `i` of `graph` is vertex
+/// `original_ids[i]` of the graph it was contracted from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contraction<W = isize> {
+    pub graph: Dgraph<W>,
+    pub original_ids: Vec<usize>,
+}
+
+impl<W> Contraction<W> {
+    pub fn contracted_id(&self, original_id: usize) -> Option<usize> {
+        self.original_ids.iter().position(|&id| id == original_id)
+    }
+}
+
+/// Collapses every vertex for which `keep` is false. Kept vertices are linked by an edge whenever
+/// one can reach the other, weighted by the length of the shortest path between them.
+pub fn contract<G: Graph>(graph: &G, keep: impl Fn(usize) -> bool) -> Contraction<Dist<G::Weight>> {
+    let original_ids: Vec<usize> = (0..graph.nb_vertices()).filter(|&id| keep(id)).collect();
+    let mut contracted = Dgraph::new(original_ids.len());
+    for (src, &original_src) in original_ids.iter().enumerate() {
+        let dist = graph.disjktra_distances(original_src);
+        for (dst, &original_dst) in original_ids.iter().enumerate() {
+            if src != dst && dist[original_dst] != Dist::<G::Weight>::INFINITY {
+                contracted.add_edge(Edge {
+                    src,
+                    dst,
+                    weight: dist[original_dst],
+                });
+            }
+        }
+    }
+    Contraction {
+        graph: contracted,
+        original_ids,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contract_path() {
+        // 0 - 1 - 2 - 3 - 4, keeping only the even vertices
+        let edges: Vec<(usize, usize, isize)> = (0..4)
+            .flat_map(|src| [(src, src + 1, 1), (src + 1, src, 2)])
+            .collect();
+        let graph = Dgraph::from_edges(5, &edges).unwrap();
+        let contraction = contract(&graph, |id| id % 2 == 0);
+        assert_eq!(contraction.original_ids, vec![0, 2, 4]);
+        assert_eq!(contraction.contracted_id(4), Some(2));
+        assert_eq!(contraction.contracted_id(3), None);
+        let weights: Vec<(usize, usize, isize)> = contraction
+            .graph
+            .edges
+            .iter()
+            .map(|edge| (edge.src, edge.dst, edge.weight))
+            .collect();
+        assert_eq!(
+            weights,
+            vec![
+                (0, 1, 2),
+                (0, 2, 4),
+                (1, 0, 4),
+                (1, 2, 2),
+                (2, 0, 8),
+                (2, 1, 4)
+            ]
+        );
+    }
+}
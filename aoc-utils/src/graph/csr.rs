@@ -0,0 +1,106 @@
+use super::{
+    dgraph::{Dgraph, Edge},
+    weight::Weight,
+    Graph,
+};
+use anyhow::{anyhow, Result};
+
+/// Frozen graph in compressed sparse row form: the outgoing edges of every vertex are stored
+/// contiguously in a single buffer, and `offsets[v]..offsets[v + 1]` delimits those of `v`.
+/// Unlike `Dgraph`, each edge is stored once and there is no allocation per vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrGraph<W = isize> {
+    offsets: Vec<usize>,
+    edges: Vec<Edge<W>>,
+}
+
+impl<W: Weight> CsrGraph<W> {
+    /// Edges keep their relative order within the neighbors of a vertex. Every edge must stay
+    /// within the `nb_vertices` vertices.
+    pub fn from_edges(
+        nb_vertices: usize,
+        edges: impl IntoIterator<Item = Edge<W>>,
+    ) -> Result<Self> {
+        let edges: Vec<Edge<W>> = edges.into_iter().collect();
+        if let Some(edge) = edges
+            .iter()
+            .find(|edge| edge.src >= nb_vertices || edge.dst >= nb_vertices)
+        {
+            return Err(anyhow!("Edge {edge:?} references an unknown vertex"));
+        }
+        Ok(Self::from_valid_edges(nb_vertices, edges))
+    }
+
+    /// `from_edges` for edges taken from a graph with the same vertices, which cannot be out of
+    /// bounds.
+    pub(super) fn from_valid_edges(nb_vertices: usize, edges: Vec<Edge<W>>) -> Self {
+        let mut edges = edges;
+        edges.sort_by_key(|edge| edge.src);
+        let mut offsets = vec![0; nb_vertices + 1];
+        for edge in edges.iter() {
+            offsets[edge.src + 1] += 1;
+        }
+        for vertex_id in 0..nb_vertices {
+            offsets[vertex_id + 1] += offsets[vertex_id];
+        }
+        Self { offsets, edges }
+    }
+}
+
+impl<W: Weight> From<&Dgraph<W>> for CsrGraph<W> {
+    fn from(graph: &Dgraph<W>) -> Self {
+        Self::from_valid_edges(
+            graph.nb_vertices(),
+            graph
+                .vertices
+                .iter()
+                .flat_map(|vertex| vertex.neighbors.iter().copied())
+                .collect(),
+        )
+    }
+}
+
+impl<W: Weight> Graph for CsrGraph<W> {
+    type Weight = W;
+
+    fn nb_vertices(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn neighbors(&self, vertex_id: usize) -> &[Edge<W>] {
+        &self.edges[self.offsets[vertex_id]..self.offsets[vertex_id + 1]]
+    }
+
+    fn edges(&self) -> impl Iterator<Item = &Edge<W>> {
+        self.edges.iter()
+    }
+
+    fn nb_edges(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{connectivity::Connectivity, shortest_path::ShortestPath};
+
+    #[test]
+    fn same_results_as_dgraph() {
+        let graph: Dgraph =
+            Dgraph::from_edges(5, &[(3, 4, 1), (0, 1, 2), (1, 3, 2), (0, 2, 1), (2, 3, 5)])
+                .unwrap();
+        let csr = CsrGraph::from(&graph);
+        assert_eq!(csr.nb_vertices(), 5);
+        assert_eq!(csr.nb_edges(), 5);
+        assert_eq!(csr.neighbors(0), graph.vertices[0].neighbors.as_slice());
+        assert!(csr.neighbors(4).is_empty());
+        assert_eq!(csr.disjktra_shortest_path(0, 4), 5);
+        assert_eq!(csr.disjktra_path(0, 4), graph.disjktra_path(0, 4));
+        assert_eq!(csr.topological_sort(), graph.topological_sort());
+
+        let edges = graph.edges.clone();
+        assert_eq!(CsrGraph::from_edges(5, edges.clone()).unwrap(), csr);
+        assert!(CsrGraph::from_edges(4, edges).is_err());
+    }
+}
@@ -1,26 +1,33 @@
-use super::shortest_path::ShortestPath;
+use super::{
+    weight::{Dist, Weight},
+    Graph,
+};
+use anyhow::{anyhow, Result};
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
-pub struct Edge {
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Edge<W = isize> {
     pub src: usize,
     pub dst: usize,
-    pub weight: isize,
+    pub weight: W,
 }
 
-#[derive(Debug, Clone)]
-pub struct Vertex {
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vertex<W = isize> {
     pub id: usize,
-    pub neighbors: Vec<Edge>,
+    pub neighbors: Vec<Edge<W>>,
 }
 
-#[derive(Debug)]
-pub struct Dgraph {
-    pub vertices: Vec<Vertex>,
-    pub edges: Vec<Edge>,
+/// Serialized as its edge list, see `Dgraph::to_json`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dgraph<W = isize> {
+    pub vertices: Vec<Vertex<W>>,
+    pub edges: Vec<Edge<W>>,
 }
 
-impl Dgraph {
+impl<W: Weight> Dgraph<W> {
     pub fn new(nb_vertices: usize) -> Self {
         let mut graph = Self {
             vertices: Vec::new(),
@@ -32,6 +39,19 @@ impl Dgraph {
         graph
     }
 
+    /// Graph with `nb_vertices` vertices and an edge for every `(src, dst, weight)`, in order.
+    /// Every edge must stay within the `nb_vertices` vertices.
+    pub fn from_edges(nb_vertices: usize, edges: &[(usize, usize, W)]) -> Result<Self> {
+        let mut graph = Self::new(nb_vertices);
+        for &(src, dst, weight) in edges {
+            if src >= nb_vertices || dst >= nb_vertices {
+                return Err(anyhow!("Edge {src} -> {dst} references an unknown vertex"));
+            }
+            graph.add_edge(Edge { src, dst, weight });
+        }
+        Ok(graph)
+    }
+
     pub fn add_vertex(&mut self) {
         let vertex = Vertex {
             id: self.vertices.len(),
@@ -40,53 +60,148 @@ impl Dgraph {
         self.vertices.push(vertex);
     }
 
-    pub fn add_edge(&mut self, edge: Edge) {
+    pub fn add_edge(&mut self, edge: Edge<W>) {
         self.edges.push(edge);
         self.vertices[edge.src].neighbors.push(edge);
     }
-}
 
-impl ShortestPath for Dgraph {
-    fn disjktra_shortest_path(&self, src: usize, dst: usize) -> isize {
-        fn vertex_id_with_min_dist(dist: &[isize], visited: &[bool]) -> Option<usize> {
-            let mut min_dist = isize::MAX;
-            let mut vertex_id_min_dist = None;
-            for (vertex_id, vertex_dist) in dist.iter().enumerate() {
-                if *vertex_dist < min_dist && !visited[vertex_id] {
-                    min_dist = *vertex_dist;
-                    vertex_id_min_dist = Some(vertex_id);
-                }
+    /// Removes the first edge going from `src` to `dst`.
+    pub fn remove_edge(&mut self, src: usize, dst: usize) -> Option<Edge<W>> {
+        let position = self
+            .edges
+            .iter()
+            .position(|edge| edge.src == src && edge.dst == dst)?;
+        let neighbors = &mut self.vertices[src].neighbors;
+        let neighbor_position = neighbors.iter().position(|edge| edge.dst == dst).unwrap();
+        neighbors.remove(neighbor_position);
+        Some(self.edges.remove(position))
+    }
+
+    /// Removes a vertex and every edge touching it. Ids are compacted: the vertices after
+    /// `vertex_id` are shifted down by one.
+    pub fn remove_vertex(&mut self, vertex_id: usize) {
+        let renumber = |edge: &mut Edge<W>| {
+            if edge.src > vertex_id {
+                edge.src -= 1;
+            }
+            if edge.dst > vertex_id {
+                edge.dst -= 1;
+            }
+        };
+        let touches = |edge: &Edge<W>| edge.src == vertex_id || edge.dst == vertex_id;
+
+        self.vertices.remove(vertex_id);
+        self.edges.retain(|edge| !touches(edge));
+        self.edges.iter_mut().for_each(renumber);
+        for vertex in self.vertices.iter_mut() {
+            if vertex.id > vertex_id {
+                vertex.id -= 1;
             }
-            vertex_id_min_dist
+            vertex.neighbors.retain(|edge| !touches(edge));
+            vertex.neighbors.iter_mut().for_each(renumber);
+        }
+    }
+
+    /// Same graph with every edge pointing the other way.
+    pub fn reversed(&self) -> Self {
+        let mut graph = Self::new(self.vertices.len());
+        for edge in self.edges.iter() {
+            graph.add_edge(Edge {
+                src: edge.dst,
+                dst: edge.src,
+                weight: edge.weight,
+            });
         }
+        graph
+    }
 
-        let nb_vertices = self.vertices.len();
-        let mut dist = (0..nb_vertices).map(|_| isize::MAX).collect::<Vec<isize>>();
-        let mut prev = (0..self.vertices.len()).map(|_| 0).collect::<Vec<usize>>();
-        let mut visited = (0..nb_vertices).map(|_| false).collect::<Vec<bool>>();
+    /// Graph made of `vertex_ids` and the edges between them. Vertex `i` of the subgraph is
+    /// vertex `vertex_ids[i]` of this graph.
+    pub fn induced_subgraph(&self, vertex_ids: &[usize]) -> Self {
+        let mut new_id = vec![None; self.vertices.len()];
+        for (i, &vertex_id) in vertex_ids.iter().enumerate() {
+            new_id[vertex_id] = Some(i);
+        }
+        let mut graph = Self::new(vertex_ids.len());
+        for edge in self.edges.iter() {
+            if let (Some(src), Some(dst)) = (new_id[edge.src], new_id[edge.dst]) {
+                graph.add_edge(Edge {
+                    src,
+                    dst,
+                    weight: edge.weight,
+                });
+            }
+        }
+        graph
+    }
 
-        dist[src] = 0;
+    /// Graph with an edge from `i` to `j` for every `matrix[[i, j]]` different from `no_edge`.
+    pub fn from_matrix(matrix: &Array2<W>, no_edge: W) -> Self {
+        Self::from_option_matrix(&matrix.map(|&weight| (weight != no_edge).then_some(weight)))
+    }
 
-        loop {
-            let cur_vertex_id = vertex_id_with_min_dist(&dist, &visited);
-            if cur_vertex_id.is_none() {
-                break;
+    /// Graph with an edge from `i` to `j` for every `Some` weight in `matrix[[i, j]]`.
+    pub fn from_option_matrix(matrix: &Array2<Option<W>>) -> Self {
+        let mut graph = Self::new(matrix.nrows());
+        for ((src, dst), weight) in matrix.indexed_iter() {
+            if let Some(weight) = *weight {
+                graph.add_edge(Edge { src, dst, weight });
             }
-            let cur_vertex_id = cur_vertex_id.unwrap();
-            visited[cur_vertex_id] = true;
-            for neighbor in self.vertices[cur_vertex_id].neighbors.iter() {
-                let new_dist = dist[cur_vertex_id] + neighbor.weight;
-                if new_dist < dist[neighbor.dst] {
-                    dist[neighbor.dst] = new_dist;
-                    prev[neighbor.dst] = cur_vertex_id;
-                }
+        }
+        graph
+    }
+
+    /// Adjacency matrix holding `no_edge` where there is no edge. Parallel edges keep the lightest
+    /// weight.
+    pub fn to_matrix(&self, no_edge: W) -> Array2<W> {
+        self.to_option_matrix()
+            .map(|weight| weight.unwrap_or(no_edge))
+    }
+
+    pub fn to_option_matrix(&self) -> Array2<Option<W>> {
+        let n = self.vertices.len();
+        let mut matrix = Array2::from_elem((n, n), None);
+        for edge in self.edges.iter() {
+            let weight = &mut matrix[[edge.src, edge.dst]];
+            if weight.is_none_or(|weight| edge.weight < weight) {
+                *weight = Some(edge.weight);
             }
         }
-        dist[dst]
+        matrix
+    }
+
+    /// Adjacency matrix as expected by `floyd_warshal`, in the type distances are summed up in:
+    /// 0 on the diagonal and `W::Dist::INFINITY` where there is no edge.
+    pub fn to_distance_matrix(&self) -> Array2<Dist<W>> {
+        let mut matrix = self
+            .to_option_matrix()
+            .map(|weight| weight.map_or(Dist::<W>::INFINITY, Dist::<W>::from));
+        matrix.diag_mut().fill(Dist::<W>::ZERO);
+        matrix
+    }
+}
+
+impl<W: Weight> Graph for Dgraph<W> {
+    type Weight = W;
+
+    fn nb_vertices(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn neighbors(&self, vertex_id: usize) -> &[Edge<W>] {
+        &self.vertices[vertex_id].neighbors
+    }
+
+    fn edges(&self) -> impl Iterator<Item = &Edge<W>> {
+        self.edges.iter()
+    }
+
+    fn nb_edges(&self) -> usize {
+        self.edges.len()
     }
 }
 
-impl fmt::Display for Dgraph {
+impl<W: fmt::Display> fmt::Display for Dgraph<W> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for vertex in self.vertices.iter() {
             writeln!(f, "-----------------------------------")?;
@@ -103,3 +218,55 @@ impl fmt::Display for Dgraph {
         write!(f, "")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::shortest_path::{floyd_warshal, ShortestPath};
+
+    #[test]
+    fn matrix_round_trip() {
+        let graph: Dgraph =
+            Dgraph::from_edges(3, &[(0, 1, 4), (0, 1, 2), (1, 2, 3), (2, 2, 1)]).unwrap();
+        let matrix = graph.to_matrix(-1);
+        assert_eq!(
+            matrix,
+            ndarray::array![[-1, 2, -1], [-1, -1, 3], [-1, -1, 1]]
+        );
+        let rebuilt = Dgraph::from_matrix(&matrix, -1);
+        assert_eq!(rebuilt.edges.len(), 3);
+        assert_eq!(
+            Dgraph::from_option_matrix(&graph.to_option_matrix()),
+            rebuilt
+        );
+
+        let mut distances = graph.to_distance_matrix();
+        floyd_warshal(&mut distances);
+        assert_eq!(distances[[0, 2]], rebuilt.disjktra_shortest_path(0, 2));
+        assert_eq!(distances[[2, 0]], isize::MAX);
+
+        // Unreachable pairs stay unreachable next to negative weights
+        let graph: Dgraph = Dgraph::from_edges(3, &[(1, 2, -3)]).unwrap();
+        let mut distances = graph.to_distance_matrix();
+        floyd_warshal(&mut distances);
+        assert_eq!(distances[[1, 2]], -3);
+        assert_eq!(distances[[0, 2]], isize::MAX);
+        assert_eq!(graph.disjktra_distances(0), vec![0, isize::MAX, isize::MAX]);
+    }
+
+    #[test]
+    fn from_edges() {
+        let graph = Dgraph::from_edges(3, &[(0, 1, 4), (2, 0, 1)]).unwrap();
+        assert_eq!(graph.vertices.len(), 3);
+        assert_eq!(
+            graph.vertices[2].neighbors,
+            vec![Edge {
+                src: 2,
+                dst: 0,
+                weight: 1
+            }]
+        );
+        assert!(Dgraph::from_edges(3, &[(0, 3, 1)]).is_err());
+        assert!(Dgraph::from_edges(3, &[(3, 0, 1)]).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use super::{
+    dgraph::{Dgraph, Edge},
+    weight::Weight,
+    Graph,
+};
+use anyhow::{anyhow, Result};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::HashSet, fmt::Write};
+
+/// Rendering options for `ToDot::to_dot`. Vertices are named by their id unless `labels` is set.
+#[derive(Default)]
+pub struct DotOptions<'a> {
+    pub labels: Option<&'a dyn Fn(usize) -> String>,
+    pub weights: bool,
+    pub highlight: &'a [usize],
+}
+
+pub trait ToDot {
+    /// GraphViz representation of the graph, to be rendered with `dot -Tsvg`.
+    fn to_dot(&self, options: &DotOptions) -> String;
+}
+
+impl<G: Graph> ToDot for G {
+    fn to_dot(&self, options: &DotOptions) -> String {
+        let highlighted_vertices: HashSet<usize> = options.highlight.iter().copied().collect();
+        let directed = self.is_directed();
+        let highlighted_edges: HashSet<(usize, usize)> = options
+            .highlight
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect();
+
+        let (mut dot, arrow) = if directed {
+            (String::from("digraph {\n"), "->")
+        } else {
+            (String::from("graph {\n"), "--")
+        };
+        for vertex_id in 0..self.nb_vertices() {
+            let label = match options.labels {
+                Some(labels) => escape(&labels(vertex_id)),
+                None => vertex_id.to_string(),
+            };
+            let _ = write!(dot, "    {} [label=\"{}\"", vertex_id, label);
+            if highlighted_vertices.contains(&vertex_id) {
+                dot.push_str(", color=red, style=bold");
+            }
+            dot.push_str("];\n");
+        }
+        // Undirected edges are only drawn once
+        for edge in self.edges().filter(|edge| directed || edge.src <= edge.dst) {
+            let mut attributes = Vec::new();
+            if options.weights {
+                attributes.push(format!("label=\"{}\"", edge.weight));
+            }
+            if highlighted_edges.contains(&(edge.src, edge.dst))
+                || (!directed && highlighted_edges.contains(&(edge.dst, edge.src)))
+            {
+                attributes.push("color=red".to_string());
+                attributes.push("penwidth=2".to_string());
+            }
+            let _ = write!(dot, "    {} {} {}", edge.src, arrow, edge.dst);
+            if !attributes.is_empty() {
+                let _ = write!(dot, " [{}]", attributes.join(", "));
+            }
+            dot.push_str(";\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Serialized form of a `Dgraph`. Only the edge list is stored, the adjacency lists are rebuilt
+/// from it.
+#[derive(Serialize, Deserialize)]
+struct JsonGraph<W> {
+    nb_vertices: usize,
+    edges: Vec<Edge<W>>,
+}
+
+impl<W: Weight> TryFrom<JsonGraph<W>> for Dgraph<W> {
+    type Error = anyhow::Error;
+    fn try_from(json: JsonGraph<W>) -> Result<Self> {
+        let mut graph = Self::new(json.nb_vertices);
+        for edge in json.edges {
+            if edge.src >= json.nb_vertices || edge.dst >= json.nb_vertices {
+                return Err(anyhow!("Edge {edge:?} references an unknown vertex"));
+            }
+            graph.add_edge(edge);
+        }
+        Ok(graph)
+    }
+}
+
+impl<W: Weight + Serialize> Serialize for Dgraph<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        JsonGraph {
+            nb_vertices: self.vertices.len(),
+            edges: self.edges.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, W: Weight + Deserialize<'de>> Deserialize<'de> for Dgraph<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = JsonGraph::deserialize(deserializer)?;
+        Self::try_from(json).map_err(serde::de::Error::custom)
+    }
+}
+
+impl<W: Weight + Serialize + DeserializeOwned> Dgraph<W> {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(data: &str) -> Result<Self> {
+        Ok(serde_json::from_str(data)?)
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dgraph::Vertex;
+
+    #[test]
+    fn export_dot() {
+        let graph: Dgraph = Dgraph::from_edges(3, &[(0, 1, 4), (1, 2, 1)]).unwrap();
+        let labels = |id: usize| ["AA", "BB", "C\"C"][id].to_string();
+        let dot = graph.to_dot(&DotOptions {
+            labels: Some(&labels),
+            weights: true,
+            highlight: &[0, 1],
+        });
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("    0 [label=\"AA\", color=red, style=bold];\n"));
+        assert!(dot.contains("    2 [label=\"C\\\"C\"];\n"));
+        assert!(dot.contains("    0 -> 1 [label=\"4\", color=red, penwidth=2];\n"));
+        assert!(dot.contains("    1 -> 2 [label=\"1\"];\n"));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let graph: Dgraph = Dgraph::from_edges(3, &[(0, 1, 4), (1, 2, 1)]).unwrap();
+        let json = graph.to_json().unwrap();
+        assert_eq!(Dgraph::from_json(&json).unwrap(), graph);
+        assert_eq!(
+            json,
+            r#"{"nb_vertices":3,"edges":[{"src":0,"dst":1,"weight":4},{"src":1,"dst":2,"weight":1}]}"#
+        );
+        assert!(Dgraph::<isize>::from_json(&json.replace("\"dst\":2", "\"dst\":7")).is_err());
+        assert!(Dgraph::<isize>::from_json(&json.replace("\"src\":1", "\"src\":3")).is_err());
+
+        let value = serde_json::to_value(&graph).unwrap();
+        assert_eq!(serde_json::from_value::<Dgraph>(value).unwrap(), graph);
+        let vertex = &graph.vertices[1];
+        let json = serde_json::to_string(vertex).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":1,"neighbors":[{"src":1,"dst":2,"weight":1}]}"#
+        );
+        assert_eq!(&serde_json::from_str::<Vertex>(&json).unwrap(), vertex);
+    }
+}
@@ -0,0 +1,302 @@
+use super::{
+    dgraph::Edge,
+    weight::{Dist, Weight},
+    Graph,
+};
+use std::collections::VecDeque;
+
+/// A cut splitting the vertices in two sides. `cut_edges` are the edges of the original graph
+/// crossing the cut and `value` is the sum of their weights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinCut<W: Weight = isize> {
+    pub value: W::Dist,
+    pub source_side: Vec<usize>,
+    pub sink_side: Vec<usize>,
+    pub cut_edges: Vec<Edge<W>>,
+}
+
+/// Flows and cut values are summed up in `W::Dist`.
+pub trait MaxFlow<W: Weight> {
+    /// Dinic's algorithm, using `Edge::weight` as the capacity.
+    fn dinic_max_flow(&self, src: usize, dst: usize) -> Dist<W>;
+    /// Minimum `src`-`dst` cut, deduced from the residual graph of the maximum flow.
+    fn min_st_cut(&self, src: usize, dst: usize) -> MinCut<W>;
+    /// Stoer-Wagner minimum cut of the whole graph. The edges of a directed graph are
+    /// considered undirected.
+    /// Returns `None` when the graph has less than two vertices.
+    fn global_min_cut(&self) -> Option<MinCut<W>>;
+}
+
+impl<G: Graph> MaxFlow<G::Weight> for G {
+    fn dinic_max_flow(&self, src: usize, dst: usize) -> Dist<G::Weight> {
+        let mut network = FlowNetwork::<Dist<G::Weight>>::new(self);
+        network.max_flow(src, dst)
+    }
+
+    fn min_st_cut(&self, src: usize, dst: usize) -> MinCut<G::Weight> {
+        let mut network = FlowNetwork::<Dist<G::Weight>>::new(self);
+        network.max_flow(src, dst);
+        let reachable = network.reachable_from(src);
+        make_cut(self, &reachable, |edge| {
+            reachable[edge.src] && !reachable[edge.dst]
+        })
+    }
+
+    fn global_min_cut(&self) -> Option<MinCut<G::Weight>> {
+        let nb_vertices = self.nb_vertices();
+        if nb_vertices < 2 {
+            return None;
+        }
+        let mut weights = vec![vec![Dist::<G::Weight>::ZERO; nb_vertices]; nb_vertices];
+        // An undirected graph already holds both directions of each edge
+        let directed = self.is_directed();
+        for edge in self
+            .edges()
+            .filter(|edge| edge.src != edge.dst && (directed || edge.src < edge.dst))
+        {
+            weights[edge.src][edge.dst] = weights[edge.src][edge.dst] + edge.weight.into();
+            weights[edge.dst][edge.src] = weights[edge.dst][edge.src] + edge.weight.into();
+        }
+        // Original vertices merged into each remaining vertex
+        let mut groups: Vec<Vec<usize>> = (0..nb_vertices).map(|v| vec![v]).collect();
+        let mut remaining: Vec<usize> = (0..nb_vertices).collect();
+        let mut best: Option<(Dist<G::Weight>, Vec<usize>)> = None;
+
+        while remaining.len() > 1 {
+            // Maximum adjacency ordering: grow a set by always adding the most tightly
+            // connected remaining vertex. The last two added are `prev` and `last`.
+            let mut added = vec![false; nb_vertices];
+            let mut connectivity = vec![Dist::<G::Weight>::ZERO; nb_vertices];
+            let (mut prev, mut last) = (remaining[0], remaining[0]);
+            for _ in 0..remaining.len() {
+                let next = *remaining
+                    .iter()
+                    .filter(|&&v| !added[v])
+                    .max_by_key(|&&v| connectivity[v])
+                    .unwrap();
+                added[next] = true;
+                prev = last;
+                last = next;
+                for &v in remaining.iter() {
+                    connectivity[v] = connectivity[v] + weights[next][v];
+                }
+            }
+
+            // The cut of the phase separates `last` from everything else
+            let cut_of_the_phase = connectivity[last] - weights[last][last];
+            if best
+                .as_ref()
+                .is_none_or(|(value, _)| cut_of_the_phase < *value)
+            {
+                best = Some((cut_of_the_phase, groups[last].clone()));
+            }
+
+            // Merge `last` into `prev`
+            let merged = std::mem::take(&mut groups[last]);
+            groups[prev].extend(merged);
+            for &v in remaining.iter() {
+                weights[prev][v] = weights[prev][v] + weights[last][v];
+                weights[v][prev] = weights[prev][v];
+            }
+            weights[prev][prev] = Dist::<G::Weight>::ZERO;
+            remaining.retain(|&v| v != last);
+        }
+
+        let (_, side) = best.unwrap();
+        let mut in_side = vec![false; nb_vertices];
+        for v in side {
+            in_side[v] = true;
+        }
+        Some(make_cut(self, &in_side, |edge| {
+            in_side[edge.src] != in_side[edge.dst] && (directed || edge.src < edge.dst)
+        }))
+    }
+}
+
+fn make_cut<G: Graph>(
+    graph: &G,
+    source_side: &[bool],
+    crosses: impl Fn(&Edge<G::Weight>) -> bool,
+) -> MinCut<G::Weight> {
+    let cut_edges: Vec<Edge<G::Weight>> = graph.edges().copied().filter(crosses).collect();
+    let (source_side, sink_side) =
+        (0..graph.nb_vertices()).partition(|&vertex_id| source_side[vertex_id]);
+    MinCut {
+        value: cut_edges
+            .iter()
+            .map(|edge| Dist::<G::Weight>::from(edge.weight))
+            .sum(),
+        source_side,
+        sink_side,
+        cut_edges,
+    }
+}
+
+/// Residual graph. Edge `2i` is the `i`th edge of the graph and `2i + 1` its reverse.
+/// Capacities are in the distance type of the graph weights.
+struct FlowNetwork<W> {
+    dst: Vec<usize>,
+    capacity: Vec<W>,
+    adjacency: Vec<Vec<usize>>,
+    level: Vec<usize>,
+    next_edge: Vec<usize>,
+}
+
+impl<W: Weight> FlowNetwork<W> {
+    fn new<G: Graph>(graph: &G) -> Self
+    where
+        W: From<G::Weight>,
+    {
+        let nb_vertices = graph.nb_vertices();
+        let mut network = Self {
+            dst: Vec::with_capacity(2 * graph.nb_edges()),
+            capacity: Vec::with_capacity(2 * graph.nb_edges()),
+            adjacency: vec![Vec::new(); nb_vertices],
+            level: vec![usize::MAX; nb_vertices],
+            next_edge: vec![0; nb_vertices],
+        };
+        for edge in graph.edges() {
+            network.adjacency[edge.src].push(network.dst.len());
+            network.dst.push(edge.dst);
+            network.capacity.push(edge.weight.into());
+            network.adjacency[edge.dst].push(network.dst.len());
+            network.dst.push(edge.src);
+            network.capacity.push(W::ZERO);
+        }
+        network
+    }
+
+    fn max_flow(&mut self, src: usize, dst: usize) -> W {
+        if src == dst {
+            return W::ZERO;
+        }
+        let mut flow = W::ZERO;
+        while self.build_levels(src, dst) {
+            self.next_edge.iter_mut().for_each(|next| *next = 0);
+            loop {
+                let pushed = self.push_flow(src, dst, W::INFINITY);
+                if pushed == W::ZERO {
+                    break;
+                }
+                flow = flow + pushed;
+            }
+        }
+        flow
+    }
+
+    /// Breadth-first search on the residual graph. Returns whether `dst` can still be reached.
+    fn build_levels(&mut self, src: usize, dst: usize) -> bool {
+        self.level.iter_mut().for_each(|level| *level = usize::MAX);
+        self.level[src] = 0;
+        let mut queue = VecDeque::from([src]);
+        while let Some(vertex_id) = queue.pop_front() {
+            for &edge_id in self.adjacency[vertex_id].iter() {
+                let next = self.dst[edge_id];
+                if self.capacity[edge_id] > W::ZERO && self.level[next] == usize::MAX {
+                    self.level[next] = self.level[vertex_id] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        self.level[dst] != usize::MAX
+    }
+
+    /// Sends at most `limit` units along level-increasing edges and returns the amount sent.
+    fn push_flow(&mut self, vertex_id: usize, dst: usize, limit: W) -> W {
+        if vertex_id == dst {
+            return limit;
+        }
+        while self.next_edge[vertex_id] < self.adjacency[vertex_id].len() {
+            let edge_id = self.adjacency[vertex_id][self.next_edge[vertex_id]];
+            let next = self.dst[edge_id];
+            if self.capacity[edge_id] > W::ZERO && self.level[next] == self.level[vertex_id] + 1 {
+                let pushed = self.push_flow(next, dst, limit.min(self.capacity[edge_id]));
+                if pushed > W::ZERO {
+                    self.capacity[edge_id] = self.capacity[edge_id] - pushed;
+                    self.capacity[edge_id ^ 1] = self.capacity[edge_id ^ 1] + pushed;
+                    return pushed;
+                }
+            }
+            self.next_edge[vertex_id] += 1;
+        }
+        W::ZERO
+    }
+
+    fn reachable_from(&self, src: usize) -> Vec<bool> {
+        let mut reachable = vec![false; self.adjacency.len()];
+        reachable[src] = true;
+        let mut stack = vec![src];
+        while let Some(vertex_id) = stack.pop() {
+            for &edge_id in self.adjacency[vertex_id].iter() {
+                let next = self.dst[edge_id];
+                if self.capacity[edge_id] > W::ZERO && !reachable[next] {
+                    reachable[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        reachable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dgraph::Dgraph;
+
+    #[test]
+    fn max_flow_and_min_cut() {
+        let graph: Dgraph = Dgraph::from_edges(
+            6,
+            &[
+                (0, 1, 16),
+                (0, 2, 13),
+                (1, 2, 10),
+                (2, 1, 4),
+                (1, 3, 12),
+                (3, 2, 9),
+                (2, 4, 14),
+                (4, 3, 7),
+                (3, 5, 20),
+                (4, 5, 4),
+            ],
+        )
+        .unwrap();
+        assert_eq!(graph.dinic_max_flow(0, 5), 23);
+        let cut = graph.min_st_cut(0, 5);
+        assert_eq!(cut.value, 23);
+        assert_eq!(cut.source_side, vec![0, 1, 2, 4]);
+        assert_eq!(cut.sink_side, vec![3, 5]);
+    }
+
+    #[test]
+    fn stoer_wagner() {
+        // Two triangles joined by a single bridge
+        let graph: Dgraph = Dgraph::from_edges(
+            6,
+            &[
+                (0, 1, 3),
+                (1, 2, 3),
+                (2, 0, 3),
+                (3, 4, 3),
+                (4, 5, 3),
+                (5, 3, 3),
+                (2, 3, 1),
+            ],
+        )
+        .unwrap();
+        let cut = graph.global_min_cut().unwrap();
+        assert_eq!(cut.value, 1);
+        assert_eq!(
+            cut.cut_edges,
+            vec![Edge {
+                src: 2,
+                dst: 3,
+                weight: 1
+            }]
+        );
+        let mut sides = [cut.source_side, cut.sink_side];
+        sides.sort();
+        assert_eq!(sides, [vec![0, 1, 2], vec![3, 4, 5]]);
+    }
+}
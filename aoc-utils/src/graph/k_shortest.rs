@@ -0,0 +1,294 @@
+use super::{
+    shortest_path::ShortestPath,
+    weight::{Dist, Weight},
+    Graph,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<W = isize> {
+    pub vertices: Vec<usize>,
+    pub cost: W,
+}
+
+/// Every shortest path from `src`: `u` is in `predecessors[v]` when an edge `u -> v` lies on a
+/// shortest path from `src` to `v`. Unreachable vertices have a distance of `W::INFINITY`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPathDag<W = isize> {
+    pub src: usize,
+    pub dist: Vec<W>,
+    pub predecessors: Vec<Vec<usize>>,
+}
+
+impl<W: Weight> ShortestPathDag<W> {
+    /// Number of distinct shortest paths from `src` to `dst`, saturating at `usize::MAX`.
+    pub fn count_paths(&self, dst: usize) -> usize {
+        if self.dist[dst] == W::INFINITY {
+            return 0;
+        }
+        let mut vertices: Vec<usize> = (0..self.dist.len())
+            .filter(|&v| self.dist[v] <= self.dist[dst])
+            .collect();
+        vertices.sort_by_key(|&v| self.dist[v]);
+        let mut count = vec![0_usize; self.dist.len()];
+        count[self.src] = 1;
+        for v in vertices.into_iter().filter(|&v| v != self.src) {
+            count[v] = self.predecessors[v]
+                .iter()
+                .fold(0_usize, |acc, &u| acc.saturating_add(count[u]));
+        }
+        count[dst]
+    }
+
+    /// Enumerates every shortest path from `src` to `dst`. Their number can grow exponentially,
+    /// see `count_paths` before calling this on large graphs.
+    pub fn paths(&self, dst: usize) -> Vec<Path<W>> {
+        if self.dist[dst] == W::INFINITY {
+            return Vec::new();
+        }
+        let mut paths = Vec::new();
+        // Walk the predecessors backward from `dst`, the partial path is reversed
+        let mut stack = vec![vec![dst]];
+        while let Some(partial) = stack.pop() {
+            let vertex_id = *partial.last().unwrap();
+            if vertex_id == self.src {
+                let mut vertices = partial;
+                vertices.reverse();
+                paths.push(Path {
+                    vertices,
+                    cost: self.dist[dst],
+                });
+                continue;
+            }
+            for &prev in self.predecessors[vertex_id].iter().rev() {
+                let mut next = partial.clone();
+                next.push(prev);
+                stack.push(next);
+            }
+        }
+        paths
+    }
+}
+
+/// Edge weights are expected to be positive, as for `ShortestPath`. Costs are in `W::Dist`.
+pub trait KShortestPaths<W: Weight> {
+    /// Yen's algorithm: the `k` cheapest paths from `src` to `dst` that never visit a vertex
+    /// twice, by increasing cost.
+    fn yen_k_shortest_paths(&self, src: usize, dst: usize, k: usize) -> Vec<Path<Dist<W>>>;
+    fn shortest_path_dag(&self, src: usize) -> ShortestPathDag<Dist<W>>;
+    /// Every path from `src` to `dst` tied for the minimum cost.
+    fn all_shortest_paths(&self, src: usize, dst: usize) -> Vec<Path<Dist<W>>>;
+}
+
+impl<G: Graph> KShortestPaths<G::Weight> for G {
+    fn yen_k_shortest_paths(&self, src: usize, dst: usize, k: usize) -> Vec<Path<Dist<G::Weight>>> {
+        let no_banned_vertices = vec![false; self.nb_vertices()];
+        let first = match restricted_disjktra(self, src, dst, &no_banned_vertices, &HashSet::new())
+        {
+            Some(path) if k > 0 => path,
+            _ => return Vec::new(),
+        };
+        let mut shortest_paths = vec![first];
+        let mut candidates = BinaryHeap::new();
+        let mut seen: HashSet<Vec<usize>> = HashSet::from([shortest_paths[0].vertices.clone()]);
+
+        while shortest_paths.len() < k {
+            let previous = shortest_paths.last().unwrap().vertices.clone();
+            for spur_index in 0..previous.len() - 1 {
+                let root = &previous[..=spur_index];
+                let spur_vertex = previous[spur_index];
+
+                // Forbid the next edge of every known path sharing this root, and the root
+                // itself, so that the spur path deviates and stays loopless
+                let banned_edges: HashSet<(usize, usize)> = shortest_paths
+                    .iter()
+                    .filter(|path| path.vertices.starts_with(root))
+                    .map(|path| (spur_vertex, path.vertices[spur_index + 1]))
+                    .collect();
+                let mut banned_vertices = vec![false; self.nb_vertices()];
+                for &vertex_id in root[..spur_index].iter() {
+                    banned_vertices[vertex_id] = true;
+                }
+
+                if let Some(spur_path) =
+                    restricted_disjktra(self, spur_vertex, dst, &banned_vertices, &banned_edges)
+                {
+                    let mut vertices = root.to_vec();
+                    vertices.extend_from_slice(&spur_path.vertices[1..]);
+                    if seen.insert(vertices.clone()) {
+                        let cost = path_cost(self, root) + spur_path.cost;
+                        candidates.push(Reverse((cost, vertices)));
+                    }
+                }
+            }
+            match candidates.pop() {
+                Some(Reverse((cost, vertices))) => shortest_paths.push(Path { vertices, cost }),
+                None => break,
+            }
+        }
+        shortest_paths
+    }
+
+    fn shortest_path_dag(&self, src: usize) -> ShortestPathDag<Dist<G::Weight>> {
+        let dist = self.disjktra_distances(src);
+        let mut predecessors = vec![Vec::new(); self.nb_vertices()];
+        for edge in self.edges() {
+            if dist[edge.src] != Dist::<G::Weight>::INFINITY
+                && edge.src != edge.dst
+                && dist[edge.src] + edge.weight.into() == dist[edge.dst]
+                && !predecessors[edge.dst].contains(&edge.src)
+            {
+                predecessors[edge.dst].push(edge.src);
+            }
+        }
+        ShortestPathDag {
+            src,
+            dist,
+            predecessors,
+        }
+    }
+
+    fn all_shortest_paths(&self, src: usize, dst: usize) -> Vec<Path<Dist<G::Weight>>> {
+        self.shortest_path_dag(src).paths(dst)
+    }
+}
+
+/// Weight of the lightest edge between each pair of consecutive vertices.
+fn path_cost<G: Graph>(graph: &G, vertices: &[usize]) -> Dist<G::Weight> {
+    vertices
+        .windows(2)
+        .map(|pair| {
+            graph
+                .neighbors(pair[0])
+                .iter()
+                .filter(|edge| edge.dst == pair[1])
+                .map(|edge| edge.weight)
+                .min()
+                .map(Dist::<G::Weight>::from)
+                .unwrap()
+        })
+        .sum()
+}
+
+fn restricted_disjktra<G: Graph>(
+    graph: &G,
+    src: usize,
+    dst: usize,
+    banned_vertices: &[bool],
+    banned_edges: &HashSet<(usize, usize)>,
+) -> Option<Path<Dist<G::Weight>>> {
+    let nb_vertices = graph.nb_vertices();
+    let mut dist = vec![Dist::<G::Weight>::INFINITY; nb_vertices];
+    let mut prev = vec![None; nb_vertices];
+    let mut heap = BinaryHeap::from([Reverse((Dist::<G::Weight>::ZERO, src))]);
+    dist[src] = Dist::<G::Weight>::ZERO;
+
+    while let Some(Reverse((cur_dist, cur_vertex_id))) = heap.pop() {
+        if cur_vertex_id == dst {
+            break;
+        }
+        if cur_dist > dist[cur_vertex_id] {
+            continue;
+        }
+        for neighbor in graph.neighbors(cur_vertex_id) {
+            if banned_vertices[neighbor.dst]
+                || banned_edges.contains(&(cur_vertex_id, neighbor.dst))
+            {
+                continue;
+            }
+            let new_dist = cur_dist.saturating_add(neighbor.weight.into());
+            if new_dist < dist[neighbor.dst] {
+                dist[neighbor.dst] = new_dist;
+                prev[neighbor.dst] = Some(cur_vertex_id);
+                heap.push(Reverse((new_dist, neighbor.dst)));
+            }
+        }
+    }
+    if dist[dst] == Dist::<G::Weight>::INFINITY {
+        return None;
+    }
+    let mut vertices = vec![dst];
+    while let Some(prev_vertex_id) = prev[*vertices.last().unwrap()] {
+        vertices.push(prev_vertex_id);
+    }
+    vertices.reverse();
+    Some(Path {
+        vertices,
+        cost: dist[dst],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dgraph::Dgraph;
+
+    #[test]
+    fn yen() {
+        // Classic example from the Wikipedia article on Yen's algorithm (C=0 ... H=5)
+        let graph: Dgraph = Dgraph::from_edges(
+            6,
+            &[
+                (0, 1, 3),
+                (0, 2, 2),
+                (1, 3, 4),
+                (2, 1, 1),
+                (2, 3, 2),
+                (2, 4, 3),
+                (3, 4, 2),
+                (3, 5, 1),
+                (4, 5, 2),
+            ],
+        )
+        .unwrap();
+        let paths = graph.yen_k_shortest_paths(0, 5, 3);
+        assert_eq!(
+            paths,
+            vec![
+                Path {
+                    vertices: vec![0, 2, 3, 5],
+                    cost: 5
+                },
+                Path {
+                    vertices: vec![0, 2, 4, 5],
+                    cost: 7
+                },
+                Path {
+                    vertices: vec![0, 1, 3, 5],
+                    cost: 8
+                },
+            ]
+        );
+        assert_eq!(graph.yen_k_shortest_paths(0, 5, 100).len(), 7);
+        assert!(graph.yen_k_shortest_paths(5, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn all_shortest_paths_on_grid() {
+        // 3x3 grid with edges going right and down: 6 paths from corner to corner
+        let mut edges = Vec::new();
+        for y in 0..3 {
+            for x in 0..3 {
+                let id = y * 3 + x;
+                if x < 2 {
+                    edges.push((id, id + 1, 1));
+                }
+                if y < 2 {
+                    edges.push((id, id + 3, 1));
+                }
+            }
+        }
+        let graph: Dgraph = Dgraph::from_edges(9, &edges).unwrap();
+        let dag = graph.shortest_path_dag(0);
+        assert_eq!(dag.count_paths(8), 6);
+        let paths = graph.all_shortest_paths(0, 8);
+        assert_eq!(paths.len(), 6);
+        assert!(paths
+            .iter()
+            .all(|path| path.cost == 4 && path.vertices.len() == 5));
+        assert_eq!(paths[0].vertices, vec![0, 1, 2, 5, 8]);
+    }
+}
@@ -0,0 +1,205 @@
+use super::{
+    dgraph::{Dgraph, Edge},
+    weight::{Dist, Weight},
+};
+use anyhow::{anyhow, Result};
+use ndarray::Array2;
+use std::{borrow::Borrow, collections::HashMap, fmt::Debug, hash::Hash};
+
+#[derive(Debug, Clone)]
+pub struct LabeledEdge<E> {
+    pub src: usize,
+    pub dst: usize,
+    pub payload: E,
+}
+
+/// Graph whose vertices are addressed by label (ex: "AA") instead of by id. Every label is
+/// interned to a vertex id, in insertion order, so the graph can be handed to `Dgraph` algorithms.
+#[derive(Debug, Clone)]
+pub struct LabeledGraph<L, N, E> {
+    labels: Vec<L>,
+    ids: HashMap<L, usize>,
+    nodes: Vec<N>,
+    edges: Vec<LabeledEdge<E>>,
+}
+
+impl<L, N, E> Default for LabeledGraph<L, N, E> {
+    fn default() -> Self {
+        Self {
+            labels: Vec::new(),
+            ids: HashMap::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<L: Clone + Eq + Hash, N, E> LabeledGraph<L, N, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the graph from text where each line describes a vertex and its outgoing edges.
+    /// `parse_line` returns `(label, node payload, [(neighbor label, edge payload)])`. Neighbors
+    /// may be declared after the line referencing them, but every neighbor must be declared, and
+    /// only once.
+    pub fn from_adjacency_list<F>(data: &str, mut parse_line: F) -> Result<Self>
+    where
+        L: Debug,
+        F: FnMut(&str) -> Result<(L, N, Vec<(L, E)>)>,
+    {
+        let mut graph = Self::new();
+        let mut adjacency = Vec::new();
+        for line in data.lines().filter(|line| !line.trim().is_empty()) {
+            let (label, node, neighbors) = parse_line(line)?;
+            if graph.id(&label).is_some() {
+                return Err(anyhow!("Vertex {label:?} is declared twice"));
+            }
+            let id = graph.add_node(label, node);
+            adjacency.push((id, neighbors));
+        }
+        for (src, neighbors) in adjacency {
+            for (dst_label, payload) in neighbors {
+                let dst = graph.id(&dst_label).ok_or_else(|| {
+                    anyhow!(
+                        "Vertex {:?} has an edge to undeclared vertex {dst_label:?}",
+                        graph.labels[src]
+                    )
+                })?;
+                graph.edges.push(LabeledEdge { src, dst, payload });
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Adds a vertex and returns its id. Adding an existing label replaces its payload.
+    pub fn add_node(&mut self, label: L, payload: N) -> usize {
+        if let Some(&id) = self.ids.get(&label) {
+            self.nodes[id] = payload;
+            return id;
+        }
+        let id = self.labels.len();
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        self.nodes.push(payload);
+        id
+    }
+
+    pub fn add_edge<Q>(&mut self, src: &Q, dst: &Q, payload: E) -> Result<()>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (src, dst) = match (self.id(src), self.id(dst)) {
+            (Some(src), Some(dst)) => (src, dst),
+            _ => return Err(anyhow!("Cannot add an edge between undeclared labels")),
+        };
+        self.edges.push(LabeledEdge { src, dst, payload });
+        Ok(())
+    }
+
+    pub fn id<Q>(&self, label: &Q) -> Option<usize>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: usize) -> &L {
+        &self.labels[id]
+    }
+
+    pub fn node(&self, id: usize) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn node_by_label<Q>(&self, label: &Q) -> Option<&N>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.id(label).map(|id| &self.nodes[id])
+    }
+
+    /// Iterates over `(id, label, payload)` in id order.
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, &L, &N)> {
+        self.labels
+            .iter()
+            .zip(self.nodes.iter())
+            .enumerate()
+            .map(|(id, (label, node))| (id, label, node))
+    }
+
+    pub fn edges(&self) -> &[LabeledEdge<E>] {
+        &self.edges
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn to_dgraph<W: Weight>(&self, weight: impl Fn(&E) -> W) -> Dgraph<W> {
+        let mut graph = Dgraph::new(self.len());
+        for edge in self.edges.iter() {
+            graph.add_edge(Edge {
+                src: edge.src,
+                dst: edge.dst,
+                weight: weight(&edge.payload),
+            });
+        }
+        graph
+    }
+
+    /// Adjacency matrix as expected by `floyd_warshal`: 0 on the diagonal and
+    /// `W::Dist::INFINITY` where there is no edge. Parallel edges keep the lightest weight.
+    pub fn to_distance_matrix<W: Weight>(&self, weight: impl Fn(&E) -> W) -> Array2<Dist<W>> {
+        self.to_dgraph(weight).to_distance_matrix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_from_adjacency_list() {
+        let data = "AA 0 BB CC\nBB 13 AA\nCC 2 BB\n";
+        let graph = LabeledGraph::from_adjacency_list(data, |line| {
+            let mut items = line.split_whitespace();
+            let label = items.next().unwrap().to_string();
+            let flow_rate: usize = items.next().unwrap().parse()?;
+            let neighbors = items.map(|n| (n.to_string(), ())).collect();
+            Ok((label, flow_rate, neighbors))
+        })
+        .unwrap();
+        assert_eq!(graph.id("CC"), Some(2));
+        assert_eq!(graph.node_by_label("BB"), Some(&13));
+        assert_eq!(graph.to_dgraph(|_| 1).edges.len(), 4);
+
+        let mat = graph.to_distance_matrix(|_| 1_usize);
+        assert_eq!(mat[[2, 1]], 1);
+        assert_eq!(mat[[1, 2]], usize::MAX);
+    }
+
+    #[test]
+    fn reject_bad_adjacency_lists() {
+        let parse_line = |line: &str| {
+            let mut items = line.split_whitespace();
+            let label = items.next().unwrap().to_string();
+            let neighbors = items.map(|n| (n.to_string(), ())).collect();
+            Ok((label, (), neighbors))
+        };
+        let error = LabeledGraph::from_adjacency_list("AA BB\nBB AA\nAA", parse_line).unwrap_err();
+        assert_eq!(error.to_string(), r#"Vertex "AA" is declared twice"#);
+        let error = LabeledGraph::from_adjacency_list("AA BB\nBB ZZ", parse_line).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Vertex "BB" has an edge to undeclared vertex "ZZ""#
+        );
+    }
+}
@@ -1,2 +1,43 @@
+pub mod all_pairs;
+pub mod bidirectional;
+pub mod connectivity;
+pub mod contraction;
+pub mod csr;
 pub mod dgraph;
+pub mod export;
+pub mod flow;
+pub mod k_shortest;
+pub mod labeled;
+pub mod orienteering;
 pub mod shortest_path;
+pub mod spanning_tree;
+pub mod traversal;
+pub mod ugraph;
+pub mod weight;
+
+use dgraph::Edge;
+use weight::Weight;
+
+/// Read-only view of a directed graph with vertices numbered `0..nb_vertices()`. The graph
+/// algorithms are implemented for every type implementing it.
+pub trait Graph {
+    type Weight: Weight;
+
+    fn nb_vertices(&self) -> usize;
+
+    /// Outgoing edges of `vertex_id`.
+    fn neighbors(&self, vertex_id: usize) -> &[Edge<Self::Weight>];
+
+    fn edges(&self) -> impl Iterator<Item = &Edge<Self::Weight>> {
+        (0..self.nb_vertices()).flat_map(|vertex_id| self.neighbors(vertex_id).iter())
+    }
+
+    fn nb_edges(&self) -> usize {
+        self.edges().count()
+    }
+
+    /// Undirected graphs hold every edge in both directions.
+    fn is_directed(&self) -> bool {
+        true
+    }
+}
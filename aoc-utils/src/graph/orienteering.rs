@@ -0,0 +1,137 @@
+use ndarray::Array2;
+use std::collections::HashMap;
+
+/// "Collect rewards at nodes within a time budget" problem. Starting from `start`, an agent
+/// travels between nodes following `distances` (ex: the output of `floyd_warshal`) and collects
+/// each target at most once. Bit `i` of a subset mask stands for `targets[i]`.
+pub struct Orienteering<'a, F> {
+    pub distances: &'a Array2<usize>,
+    pub start: usize,
+    pub targets: &'a [usize],
+    pub budget: usize,
+    /// Time spent at a target to collect its reward, on top of the travel time.
+    pub service_time: usize,
+    /// Value of collecting `targets[i]` when `t` time units are left once it is collected,
+    /// called as `reward(i, t)`.
+    pub reward: F,
+}
+
+impl<F: Fn(usize, usize) -> usize> Orienteering<'_, F> {
+    /// Best value reachable for every subset of targets that can be collected within the budget.
+    pub fn best_per_subset(&self) -> HashMap<u64, usize> {
+        assert!(self.targets.len() <= 64, "targets do not fit in a u64 mask");
+        let mut best = HashMap::new();
+        self.explore(self.start, self.budget, 0, 0, &mut best);
+        best
+    }
+
+    /// Best value for a single agent.
+    pub fn best(&self) -> usize {
+        self.best_per_subset().into_values().max().unwrap_or(0)
+    }
+
+    fn explore(
+        &self,
+        position: usize,
+        time_left: usize,
+        visited: u64,
+        value: usize,
+        best: &mut HashMap<u64, usize>,
+    ) {
+        let best_value = best.entry(visited).or_insert(0);
+        *best_value = (*best_value).max(value);
+
+        for (i, &target) in self.targets.iter().enumerate() {
+            if visited & (1 << i) != 0 {
+                continue;
+            }
+            let cost = self.distances[[position, target]].saturating_add(self.service_time);
+            if cost > time_left {
+                continue;
+            }
+            let time_left = time_left - cost;
+            self.explore(
+                target,
+                time_left,
+                visited | (1 << i),
+                value + (self.reward)(i, time_left),
+                best,
+            );
+        }
+    }
+}
+
+/// Best total of two agents collecting disjoint subsets, from the output of `best_per_subset`.
+pub fn best_disjoint_pair(best_per_subset: &HashMap<u64, usize>) -> usize {
+    let mut subsets: Vec<(u64, usize)> = best_per_subset
+        .iter()
+        .map(|(&mask, &value)| (mask, value))
+        .collect();
+    subsets.sort_unstable_by_key(|&(_, value)| std::cmp::Reverse(value));
+
+    let mut best = 0;
+    for (i, &(mask_a, value_a)) in subsets.iter().enumerate() {
+        if value_a * 2 <= best {
+            break;
+        }
+        for &(mask_b, value_b) in subsets[i..].iter() {
+            if value_a + value_b <= best {
+                break;
+            }
+            if mask_a & mask_b == 0 {
+                best = value_a + value_b;
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{labeled::LabeledGraph, shortest_path::floyd_warshal};
+
+    const VALVES: &str = "AA 0 DD II BB
+BB 13 CC AA
+CC 2 DD BB
+DD 20 CC AA EE
+EE 3 FF DD
+FF 0 EE GG
+GG 0 FF HH
+HH 22 GG
+II 0 AA JJ
+JJ 21 II";
+
+    #[test]
+    fn release_pressure() {
+        let graph = LabeledGraph::from_adjacency_list(VALVES, |line| {
+            let mut items = line.split_whitespace();
+            let label = items.next().unwrap().to_string();
+            let flow_rate: usize = items.next().unwrap().parse()?;
+            let neighbors = items.map(|n| (n.to_string(), ())).collect();
+            Ok((label, flow_rate, neighbors))
+        })
+        .unwrap();
+        let mut distances = graph.to_distance_matrix(|_| 1_usize);
+        floyd_warshal(&mut distances);
+        let targets: Vec<usize> = graph
+            .nodes()
+            .filter(|(_, _, &flow_rate)| flow_rate > 0)
+            .map(|(id, _, _)| id)
+            .collect();
+        let orienteering = |budget| Orienteering {
+            distances: &distances,
+            start: graph.id("AA").unwrap(),
+            targets: &targets,
+            budget,
+            service_time: 1,
+            reward: |i, time_left| graph.node(targets[i]) * time_left,
+        };
+
+        assert_eq!(orienteering(30).best(), 1651);
+        assert_eq!(
+            best_disjoint_pair(&orienteering(26).best_per_subset()),
+            1707
+        );
+    }
+}
@@ -1,15 +1,30 @@
+use super::{
+    weight::{Dist, Weight},
+    Graph,
+};
 use ndarray::Array2;
 
-pub trait ShortestPath {
-    fn disjktra_shortest_path(&self, src: usize, dst: usize) -> isize;
+/// Distances are summed up in `W::Dist`, so compact weights do not overflow.
+pub trait ShortestPath<W: Weight> {
+    fn disjktra_shortest_path(&self, src: usize, dst: usize) -> Dist<W>;
+    /// Distance from `src` to every vertex, `W::Dist::INFINITY` for the unreachable ones.
+    fn disjktra_distances(&self, src: usize) -> Vec<Dist<W>>;
+    /// Vertices visited from `src` to `dst` (both included), `None` if `dst` is unreachable.
+    fn disjktra_path(&self, src: usize, dst: usize) -> Option<Vec<usize>>;
 }
 
-pub fn floyd_warshal(w: &mut Array2<usize>) {
+/// Missing edges are expected to weigh `W::INFINITY`. The matrix holds the distances, see
+/// `Dgraph::to_distance_matrix` to build it from compact weights.
+pub fn floyd_warshal<W: Weight>(w: &mut Array2<W>) {
     let n = w.shape()[0];
 
     for k in 0..n {
         for i in 0..n {
             for j in 0..n {
+                // A negative weight would make a missing edge look shorter than `W::INFINITY`
+                if w[[i, k]] == W::INFINITY || w[[k, j]] == W::INFINITY {
+                    continue;
+                }
                 if w[[i, j]] > w[[i, k]].saturating_add(w[[k, j]]) {
                     w[[i, j]] = w[[i, k]].saturating_add(w[[k, j]]);
                 }
@@ -17,3 +32,71 @@ pub fn floyd_warshal(w: &mut Array2<usize>) {
         }
     }
 }
+
+fn disjktra<G: Graph>(graph: &G, src: usize) -> (Vec<Dist<G::Weight>>, Vec<Option<usize>>) {
+    fn vertex_id_with_min_dist<W: Weight>(dist: &[W], visited: &[bool]) -> Option<usize> {
+        let mut min_dist = W::INFINITY;
+        let mut vertex_id_min_dist = None;
+        for (vertex_id, vertex_dist) in dist.iter().enumerate() {
+            if *vertex_dist < min_dist && !visited[vertex_id] {
+                min_dist = *vertex_dist;
+                vertex_id_min_dist = Some(vertex_id);
+            }
+        }
+        vertex_id_min_dist
+    }
+
+    let nb_vertices = graph.nb_vertices();
+    let mut dist = (0..nb_vertices)
+        .map(|_| Dist::<G::Weight>::INFINITY)
+        .collect::<Vec<Dist<G::Weight>>>();
+    let mut prev = (0..nb_vertices)
+        .map(|_| None)
+        .collect::<Vec<Option<usize>>>();
+    let mut visited = (0..nb_vertices).map(|_| false).collect::<Vec<bool>>();
+
+    dist[src] = Dist::<G::Weight>::ZERO;
+
+    while let Some(cur_vertex_id) = vertex_id_with_min_dist(&dist, &visited) {
+        visited[cur_vertex_id] = true;
+        // A negative weight would make an unreachable vertex look reachable
+        if dist[cur_vertex_id] == Dist::<G::Weight>::INFINITY {
+            continue;
+        }
+        for neighbor in graph.neighbors(cur_vertex_id) {
+            let new_dist = dist[cur_vertex_id].saturating_add(neighbor.weight.into());
+            if new_dist < dist[neighbor.dst] {
+                dist[neighbor.dst] = new_dist;
+                prev[neighbor.dst] = Some(cur_vertex_id);
+            }
+        }
+    }
+    (dist, prev)
+}
+
+impl<G: Graph> ShortestPath<G::Weight> for G {
+    fn disjktra_shortest_path(&self, src: usize, dst: usize) -> Dist<G::Weight> {
+        let (dist, _) = disjktra(self, src);
+        dist[dst]
+    }
+
+    fn disjktra_distances(&self, src: usize) -> Vec<Dist<G::Weight>> {
+        let (dist, _) = disjktra(self, src);
+        dist
+    }
+
+    fn disjktra_path(&self, src: usize, dst: usize) -> Option<Vec<usize>> {
+        let (dist, prev) = disjktra(self, src);
+        if dist[dst] == Dist::<G::Weight>::INFINITY {
+            return None;
+        }
+        let mut path = vec![dst];
+        let mut cur_vertex_id = dst;
+        while let Some(prev_vertex_id) = prev[cur_vertex_id] {
+            path.push(prev_vertex_id);
+            cur_vertex_id = prev_vertex_id;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
@@ -0,0 +1,112 @@
+use super::{
+    dgraph::Edge,
+    weight::{Dist, Weight},
+    Graph,
+};
+use crate::union_find::UnionFind;
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// Edges chosen for a minimum spanning tree. When the graph is not connected, this is a
+/// minimum spanning forest: one tree per connected component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningTree<W: Weight = isize> {
+    pub edges: Vec<Edge<W>>,
+    pub total_weight: W::Dist,
+}
+
+/// Edges are considered undirected by both algorithms.
+pub trait MinSpanningTree<W: Weight> {
+    fn kruskal_mst(&self) -> SpanningTree<W>;
+    fn prim_mst(&self) -> SpanningTree<W>;
+}
+
+impl<G: Graph> MinSpanningTree<G::Weight> for G {
+    fn kruskal_mst(&self) -> SpanningTree<G::Weight> {
+        let mut edges: Vec<Edge<G::Weight>> = self.edges().copied().collect();
+        edges.sort_by_key(|edge| edge.weight);
+        let mut components = UnionFind::new(self.nb_vertices());
+        let edges: Vec<Edge<G::Weight>> = edges
+            .into_iter()
+            .filter(|edge| components.union(edge.src, edge.dst))
+            .collect();
+        SpanningTree {
+            total_weight: edges
+                .iter()
+                .map(|edge| Dist::<G::Weight>::from(edge.weight))
+                .sum(),
+            edges,
+        }
+    }
+
+    fn prim_mst(&self) -> SpanningTree<G::Weight> {
+        let nb_vertices = self.nb_vertices();
+        let all_edges: Vec<Edge<G::Weight>> = self.edges().copied().collect();
+        let mut undirected_neighbors = vec![Vec::new(); nb_vertices];
+        for (edge_id, edge) in all_edges.iter().enumerate() {
+            undirected_neighbors[edge.src].push((edge.dst, edge_id));
+            undirected_neighbors[edge.dst].push((edge.src, edge_id));
+        }
+
+        let mut in_tree = vec![false; nb_vertices];
+        let mut edges = Vec::new();
+        for root in 0..nb_vertices {
+            if in_tree[root] {
+                continue;
+            }
+            in_tree[root] = true;
+            // (weight, edge id, vertex reached through the edge)
+            let mut heap = BinaryHeap::new();
+            for &(neighbor, edge_id) in undirected_neighbors[root].iter() {
+                heap.push(Reverse((all_edges[edge_id].weight, edge_id, neighbor)));
+            }
+            while let Some(Reverse((_, edge_id, vertex_id))) = heap.pop() {
+                if in_tree[vertex_id] {
+                    continue;
+                }
+                in_tree[vertex_id] = true;
+                edges.push(all_edges[edge_id]);
+                for &(neighbor, edge_id) in undirected_neighbors[vertex_id].iter() {
+                    if !in_tree[neighbor] {
+                        heap.push(Reverse((all_edges[edge_id].weight, edge_id, neighbor)));
+                    }
+                }
+            }
+        }
+        SpanningTree {
+            total_weight: edges
+                .iter()
+                .map(|edge| Dist::<G::Weight>::from(edge.weight))
+                .sum(),
+            edges,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dgraph::Dgraph;
+
+    #[test]
+    fn kruskal_and_prim() {
+        let graph: Dgraph = Dgraph::from_edges(
+            6,
+            &[
+                (0, 1, 4),
+                (0, 2, 1),
+                (2, 1, 2),
+                (1, 3, 5),
+                (2, 3, 8),
+                (3, 0, 7),
+                (4, 5, 3),
+            ],
+        )
+        .unwrap();
+        let kruskal = graph.kruskal_mst();
+        let prim = graph.prim_mst();
+        assert_eq!(kruskal.total_weight, 11);
+        assert_eq!(prim.total_weight, 11);
+        assert_eq!(kruskal.edges.len(), 4);
+        assert_eq!(prim.edges.len(), 4);
+    }
+}
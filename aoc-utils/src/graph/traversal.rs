@@ -0,0 +1,226 @@
+use super::{dgraph::Edge, Graph};
+use std::mem;
+
+/// Step of a depth-first search. On an undirected graph, the edge going back to the parent of
+/// a vertex is reported as a back edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DfsEvent<W = isize> {
+    Discover(usize),
+    /// Edge leading to a vertex seen for the first time.
+    TreeEdge(Edge<W>),
+    /// Edge to a vertex still being explored, closing a cycle.
+    BackEdge(Edge<W>),
+    /// Edge to an already finished descendant.
+    ForwardEdge(Edge<W>),
+    /// Edge to an already finished vertex of another branch or tree.
+    CrossEdge(Edge<W>),
+    /// Every vertex reachable from this one has been explored.
+    Finish(usize),
+}
+
+/// Lazy walks over a graph. Neighbors are explored in the order of `Graph::neighbors`.
+pub trait Traversal<W> {
+    /// Vertices grouped by their number of edges from `src`, starting with `[src]`.
+    fn bfs_layers(&self, src: usize) -> impl Iterator<Item = Vec<usize>>;
+    fn dfs_preorder(&self, src: usize) -> impl Iterator<Item = usize>;
+    fn dfs_postorder(&self, src: usize) -> impl Iterator<Item = usize>;
+    fn dfs_events(&self, src: usize) -> impl Iterator<Item = DfsEvent<W>>;
+    /// Events of a depth-first search restarted from every vertex left unvisited, by id.
+    fn dfs_forest_events(&self) -> impl Iterator<Item = DfsEvent<W>>;
+}
+
+impl<G: Graph> Traversal<G::Weight> for G {
+    fn bfs_layers(&self, src: usize) -> impl Iterator<Item = Vec<usize>> {
+        let mut visited = vec![false; self.nb_vertices()];
+        visited[src] = true;
+        BfsLayers {
+            graph: self,
+            visited,
+            layer: vec![src],
+        }
+    }
+
+    fn dfs_preorder(&self, src: usize) -> impl Iterator<Item = usize> {
+        self.dfs_events(src).filter_map(|event| match event {
+            DfsEvent::Discover(vertex_id) => Some(vertex_id),
+            _ => None,
+        })
+    }
+
+    fn dfs_postorder(&self, src: usize) -> impl Iterator<Item = usize> {
+        self.dfs_events(src).filter_map(|event| match event {
+            DfsEvent::Finish(vertex_id) => Some(vertex_id),
+            _ => None,
+        })
+    }
+
+    fn dfs_events(&self, src: usize) -> impl Iterator<Item = DfsEvent<G::Weight>> {
+        DfsEvents::new(self, vec![src])
+    }
+
+    fn dfs_forest_events(&self) -> impl Iterator<Item = DfsEvent<G::Weight>> {
+        DfsEvents::new(self, (0..self.nb_vertices()).rev().collect())
+    }
+}
+
+struct BfsLayers<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    layer: Vec<usize>,
+}
+
+impl<G: Graph> Iterator for BfsLayers<'_, G> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.layer.is_empty() {
+            return None;
+        }
+        let mut next_layer = Vec::new();
+        for &vertex_id in self.layer.iter() {
+            for neighbor in self.graph.neighbors(vertex_id) {
+                if !self.visited[neighbor.dst] {
+                    self.visited[neighbor.dst] = true;
+                    next_layer.push(neighbor.dst);
+                }
+            }
+        }
+        Some(mem::replace(&mut self.layer, next_layer))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    New,
+    Active,
+    Finished,
+}
+
+/// Iterative depth-first search, yielding one event per call to `next`.
+struct DfsEvents<'a, G: Graph> {
+    graph: &'a G,
+    /// Vertices to start from once the stack is empty, the next one last
+    roots: Vec<usize>,
+    /// Vertices being explored along with the index of their next edge to follow
+    stack: Vec<(usize, usize)>,
+    state: Vec<State>,
+    discovery_time: Vec<usize>,
+    time: usize,
+    /// Discovery of the vertex reached by the tree edge just returned
+    pending: Option<DfsEvent<G::Weight>>,
+}
+
+impl<'a, G: Graph> DfsEvents<'a, G> {
+    fn new(graph: &'a G, roots: Vec<usize>) -> Self {
+        Self {
+            graph,
+            roots,
+            stack: Vec::new(),
+            state: vec![State::New; graph.nb_vertices()],
+            discovery_time: vec![0; graph.nb_vertices()],
+            time: 0,
+            pending: None,
+        }
+    }
+
+    fn discover(&mut self, vertex_id: usize) -> DfsEvent<G::Weight> {
+        self.state[vertex_id] = State::Active;
+        self.discovery_time[vertex_id] = self.time;
+        self.time += 1;
+        self.stack.push((vertex_id, 0));
+        DfsEvent::Discover(vertex_id)
+    }
+}
+
+impl<G: Graph> Iterator for DfsEvents<'_, G> {
+    type Item = DfsEvent<G::Weight>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+        let Some(&mut (vertex_id, ref mut next_edge)) = self.stack.last_mut() else {
+            while let Some(root) = self.roots.pop() {
+                if self.state[root] == State::New {
+                    return Some(self.discover(root));
+                }
+            }
+            return None;
+        };
+
+        let Some(&edge) = self.graph.neighbors(vertex_id).get(*next_edge) else {
+            self.stack.pop();
+            self.state[vertex_id] = State::Finished;
+            return Some(DfsEvent::Finish(vertex_id));
+        };
+        *next_edge += 1;
+        Some(match self.state[edge.dst] {
+            State::New => {
+                self.pending = Some(self.discover(edge.dst));
+                DfsEvent::TreeEdge(edge)
+            }
+            State::Active => DfsEvent::BackEdge(edge),
+            State::Finished if self.discovery_time[edge.src] < self.discovery_time[edge.dst] => {
+                DfsEvent::ForwardEdge(edge)
+            }
+            State::Finished => DfsEvent::CrossEdge(edge),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dgraph::Dgraph;
+
+    #[test]
+    fn walks() {
+        // 0 -> 1 -> 2 -> 0, 0 -> 2, 3 -> 2 and 1 -> 4
+        let graph: Dgraph = Dgraph::from_edges(
+            5,
+            &[
+                (0, 1, 1),
+                (0, 2, 1),
+                (1, 2, 1),
+                (1, 4, 1),
+                (2, 0, 1),
+                (3, 2, 1),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            graph.bfs_layers(0).collect::<Vec<_>>(),
+            vec![vec![0], vec![1, 2], vec![4]]
+        );
+        assert_eq!(graph.dfs_preorder(0).collect::<Vec<_>>(), vec![0, 1, 2, 4]);
+        assert_eq!(graph.dfs_postorder(0).collect::<Vec<_>>(), vec![2, 4, 1, 0]);
+
+        let edge = |src, dst| Edge {
+            src,
+            dst,
+            weight: 1,
+        };
+        let events: Vec<DfsEvent> = graph.dfs_forest_events().collect();
+        assert_eq!(
+            events,
+            vec![
+                DfsEvent::Discover(0),
+                DfsEvent::TreeEdge(edge(0, 1)),
+                DfsEvent::Discover(1),
+                DfsEvent::TreeEdge(edge(1, 2)),
+                DfsEvent::Discover(2),
+                DfsEvent::BackEdge(edge(2, 0)),
+                DfsEvent::Finish(2),
+                DfsEvent::TreeEdge(edge(1, 4)),
+                DfsEvent::Discover(4),
+                DfsEvent::Finish(4),
+                DfsEvent::Finish(1),
+                DfsEvent::ForwardEdge(edge(0, 2)),
+                DfsEvent::Finish(0),
+                DfsEvent::Discover(3),
+                DfsEvent::CrossEdge(edge(3, 2)),
+                DfsEvent::Finish(3),
+            ]
+        );
+    }
+}
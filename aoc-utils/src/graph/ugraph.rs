@@ -0,0 +1,143 @@
+use super::{
+    dgraph::{Dgraph, Edge},
+    weight::Weight,
+    Graph,
+};
+
+/// Undirected graph. Every edge is stored in both directions, so `neighbors` and `edges` see
+/// it from both ends, while `undirected_edges` lists it once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ugraph<W = isize> {
+    graph: Dgraph<W>,
+}
+
+impl<W: Weight> Ugraph<W> {
+    pub fn new(nb_vertices: usize) -> Self {
+        Self {
+            graph: Dgraph::new(nb_vertices),
+        }
+    }
+
+    pub fn add_vertex(&mut self) {
+        self.graph.add_vertex();
+    }
+
+    pub fn add_edge(&mut self, edge: Edge<W>) {
+        self.graph.add_edge(edge);
+        if edge.src != edge.dst {
+            self.graph.add_edge(Edge {
+                src: edge.dst,
+                dst: edge.src,
+                weight: edge.weight,
+            });
+        }
+    }
+
+    /// Removes the first edge between `a` and `b`, whichever way it was added.
+    pub fn remove_edge(&mut self, a: usize, b: usize) -> Option<Edge<W>> {
+        let edge = self.graph.remove_edge(a, b)?;
+        if a != b {
+            self.graph.remove_edge(b, a);
+        }
+        Some(edge)
+    }
+
+    /// Removes a vertex and its edges, shifting the ids after `vertex_id` down by one.
+    pub fn remove_vertex(&mut self, vertex_id: usize) {
+        self.graph.remove_vertex(vertex_id);
+    }
+
+    /// Vertex `i` of the subgraph is vertex `vertex_ids[i]` of this graph.
+    pub fn induced_subgraph(&self, vertex_ids: &[usize]) -> Self {
+        Self {
+            graph: self.graph.induced_subgraph(vertex_ids),
+        }
+    }
+
+    /// Every edge once, oriented so that `src <= dst`.
+    pub fn undirected_edges(&self) -> impl Iterator<Item = &Edge<W>> {
+        self.graph.edges.iter().filter(|edge| edge.src <= edge.dst)
+    }
+
+    /// Directed graph holding both directions of every edge.
+    pub fn as_dgraph(&self) -> &Dgraph<W> {
+        &self.graph
+    }
+}
+
+impl<W: Weight> Graph for Ugraph<W> {
+    type Weight = W;
+
+    fn nb_vertices(&self) -> usize {
+        self.graph.vertices.len()
+    }
+
+    fn neighbors(&self, vertex_id: usize) -> &[Edge<W>] {
+        &self.graph.vertices[vertex_id].neighbors
+    }
+
+    fn edges(&self) -> impl Iterator<Item = &Edge<W>> {
+        self.graph.edges.iter()
+    }
+
+    fn nb_edges(&self) -> usize {
+        self.graph.edges.len()
+    }
+
+    fn is_directed(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{connectivity::Connectivity, flow::MaxFlow};
+
+    fn edge(src: usize, dst: usize, weight: isize) -> Edge {
+        Edge { src, dst, weight }
+    }
+
+    #[test]
+    fn dgraph_mutations() {
+        let mut graph = Dgraph::new(4);
+        for (src, dst) in [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)] {
+            graph.add_edge(edge(src, dst, 1));
+        }
+        assert_eq!(graph.remove_edge(0, 2), Some(edge(0, 2, 1)));
+        assert_eq!(graph.remove_edge(0, 2), None);
+        assert_eq!(graph.reversed().vertices[1].neighbors, vec![edge(1, 0, 1)]);
+
+        let subgraph = graph.induced_subgraph(&[2, 3, 0]);
+        assert_eq!(subgraph.edges, vec![edge(0, 1, 1), edge(1, 2, 1)]);
+
+        graph.remove_vertex(1);
+        assert_eq!(graph.vertices.len(), 3);
+        assert_eq!(graph.edges, vec![edge(1, 2, 1), edge(2, 0, 1)]);
+        assert_eq!(graph.vertices[2].id, 2);
+        assert_eq!(graph.vertices[1].neighbors, vec![edge(1, 2, 1)]);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        let mut graph = Ugraph::new(4);
+        graph.add_edge(edge(0, 1, 2));
+        graph.add_edge(edge(2, 1, 3));
+        graph.add_edge(edge(3, 3, 1));
+        assert_eq!(graph.nb_edges(), 5);
+        assert_eq!(graph.undirected_edges().count(), 3);
+        assert_eq!(graph.neighbors(1).len(), 2);
+        assert_eq!(graph.global_min_cut().unwrap().value, 0);
+        assert_eq!(graph.tarjan_scc(), vec![vec![3], vec![0, 1, 2]]);
+
+        graph.add_edge(edge(3, 2, 1));
+        let cut = graph.global_min_cut().unwrap();
+        assert_eq!(cut.value, 1);
+        assert_eq!(cut.cut_edges, vec![edge(2, 3, 1)]);
+
+        assert_eq!(graph.remove_edge(1, 2), Some(edge(1, 2, 3)));
+        assert_eq!(graph.neighbors(2), &[edge(2, 3, 1)]);
+        graph.remove_vertex(0);
+        assert_eq!(graph.undirected_edges().count(), 2);
+    }
+}
@@ -0,0 +1,134 @@
+use ordered_float::OrderedFloat;
+use std::{
+    fmt::{Debug, Display},
+    iter::Sum,
+    ops::{Add, Sub},
+};
+
+/// Numeric type of the edge weights. Floats have to be wrapped in `OrderedFloat` so that they
+/// can be compared and pushed on a heap.
+pub trait Weight:
+    Copy + Ord + Debug + Display + Add<Output = Self> + Sub<Output = Self> + Sum
+{
+    /// Type in which weights are summed up (distances, flows, cut values...), wider than the
+    /// weight itself for the compact integer types.
+    type Dist: Weight + From<Self>;
+
+    const ZERO: Self;
+    /// Distance to the unreachable vertices.
+    const INFINITY: Self;
+
+    /// Addition that stops at `INFINITY` instead of overflowing.
+    fn saturating_add(self, other: Self) -> Self;
+}
+
+/// Sum of weights of type `W`.
+pub type Dist<W> = <W as Weight>::Dist;
+
+macro_rules! impl_integer_weight {
+    ($($int:ty => $dist:ty),*) => {
+        $(
+            impl Weight for $int {
+                type Dist = $dist;
+
+                const ZERO: Self = 0;
+                const INFINITY: Self = <$int>::MAX;
+
+                fn saturating_add(self, other: Self) -> Self {
+                    <$int>::saturating_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_weight!(
+    u8 => u32,
+    u16 => u64,
+    u32 => u64,
+    u64 => u64,
+    usize => usize,
+    i8 => i32,
+    i16 => i64,
+    i32 => i64,
+    i64 => i64,
+    isize => isize
+);
+
+macro_rules! impl_float_weight {
+    ($($float:ty),*) => {
+        $(
+            impl Weight for OrderedFloat<$float> {
+                type Dist = Self;
+
+                const ZERO: Self = OrderedFloat(0.0);
+                const INFINITY: Self = OrderedFloat(<$float>::INFINITY);
+
+                fn saturating_add(self, other: Self) -> Self {
+                    self + other
+                }
+            }
+        )*
+    };
+}
+
+impl_float_weight!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        dgraph::{Dgraph, Edge},
+        flow::MaxFlow,
+        shortest_path::{floyd_warshal, ShortestPath},
+        ugraph::Ugraph,
+    };
+    use ndarray::array;
+
+    #[test]
+    fn compact_and_float_weights() {
+        // Distances above `u8::MAX` are summed up in `u32`
+        let mut graph: Dgraph<u8> = Dgraph::from_edges(3, &[(0, 1, 200), (1, 2, 100)]).unwrap();
+        assert_eq!(graph.disjktra_shortest_path(0, 2), 300_u32);
+        assert_eq!(graph.disjktra_path(0, 2), Some(vec![0, 1, 2]));
+        assert_eq!(graph.disjktra_shortest_path(2, 0), u32::INFINITY);
+        assert_eq!(graph.disjktra_path(2, 0), None);
+
+        graph.add_edge(Edge {
+            src: 2,
+            dst: 0,
+            weight: 1,
+        });
+        let mut w = graph.to_distance_matrix();
+        floyd_warshal(&mut w);
+        assert_eq!(w, array![[0, 200, 300], [101, 0, 100], [1, 201, 0]]);
+
+        // `u8::MAX` is a weight like any other, only `u32::MAX` means unreachable
+        let graph: Dgraph<u8> = Dgraph::from_edges(2, &[(0, 1, u8::MAX)]).unwrap();
+        let mut w = graph.to_distance_matrix();
+        floyd_warshal(&mut w);
+        assert_eq!(w[[0, 1]], 255);
+        assert_eq!(graph.disjktra_distances(0), w.row(0).to_vec());
+        assert_eq!(graph.disjktra_path(0, 1), Some(vec![0, 1]));
+
+        let mut graph: Ugraph<u8> = Ugraph::new(3);
+        for (src, dst) in [(0, 1), (1, 2), (2, 0)] {
+            graph.add_edge(Edge {
+                src,
+                dst,
+                weight: 200,
+            });
+        }
+        assert_eq!(graph.global_min_cut().unwrap().value, 400_u32);
+        assert_eq!(graph.dinic_max_flow(0, 1), 400_u32);
+
+        let graph = Dgraph::from_edges(
+            3,
+            &[(0, 1, 0.5), (1, 2, 0.25), (0, 2, 1.0)]
+                .map(|(src, dst, weight)| (src, dst, OrderedFloat(weight))),
+        )
+        .unwrap();
+        assert_eq!(graph.disjktra_shortest_path(0, 2), OrderedFloat(0.75));
+        assert_eq!(graph.disjktra_path(0, 2), Some(vec![0, 1, 2]));
+    }
+}
@@ -1 +1,3 @@
 pub mod graph;
+pub mod records;
+pub mod union_find;
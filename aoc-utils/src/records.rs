@@ -0,0 +1,27 @@
+/// Groups of consecutive non-blank lines, as in puzzle inputs where records are separated by
+/// blank lines. Record `i` is the `i`th group of the input: leading, trailing and repeated
+/// blank lines never produce empty records. Line endings may be `\n` or `\r\n`.
+pub fn records(data: &str) -> impl Iterator<Item = Vec<&str>> {
+    let mut lines = data.lines().peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|line| line.trim().is_empty()).is_some() {}
+        let mut record = Vec::new();
+        while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+            record.push(line);
+        }
+        (!record.is_empty()).then_some(record)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_records() {
+        let data = "\r\n1000\r\n2000\r\n\r\n\r\n\r\n0\r\n\n4000";
+        let records: Vec<Vec<&str>> = records(data).collect();
+        assert_eq!(records, vec![vec!["1000", "2000"], vec!["0"], vec!["4000"]]);
+        assert_eq!(super::records("\n\n").count(), 0);
+    }
+}
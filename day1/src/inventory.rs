@@ -0,0 +1,160 @@
+use anyhow::{Error, Result};
+use aoc_utils::records::records;
+use serde::Serialize;
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Write, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Elf {
+    /// Position of the elf in the input, starting at 0
+    pub index: usize,
+    /// Calories of each food item carried
+    pub items: Vec<usize>,
+    pub total: usize,
+}
+
+/// Food items carried by every elf.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inventory {
+    elves: Vec<Elf>,
+}
+
+impl FromStr for Inventory {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let elves = records(s)
+            .enumerate()
+            .map(|(index, lines)| {
+                let items = lines
+                    .iter()
+                    .map(|calories| calories.trim().parse())
+                    .collect::<Result<Vec<usize>, _>>()?;
+                Ok(Elf {
+                    index,
+                    total: items.iter().sum(),
+                    items,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { elves })
+    }
+}
+
+impl Inventory {
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    pub fn elf(&self, index: usize) -> Option<&Elf> {
+        self.elves.get(index)
+    }
+
+    /// The `k` elves carrying the most calories, from the richest. Ties go to the first elf.
+    pub fn top_k(&self, k: usize) -> Vec<&Elf> {
+        // Min-heap of the best elves seen so far, the weakest of them on top
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for elf in self.elves.iter() {
+            heap.push(Reverse((elf.total, Reverse(elf.index))));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((_, Reverse(index)))| &self.elves[index])
+            .collect()
+    }
+
+    /// 1 for the elf carrying the most calories. Elves carrying the same amount share a rank.
+    pub fn rank(&self, index: usize) -> Option<usize> {
+        let total = self.elf(index)?.total;
+        Some(1 + self.elves.iter().filter(|elf| elf.total > total).count())
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        let totals = self.sorted_totals();
+        let middle = totals.len() / 2;
+        match totals.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(totals[middle] as f64),
+            _ => Some((totals[middle - 1] + totals[middle]) as f64 / 2.0),
+        }
+    }
+
+    /// Smallest total such that at least `p` percent of the elves carry no more than it
+    /// (nearest-rank method). `p` is clamped to `0..=100`.
+    pub fn percentile(&self, p: f64) -> Option<usize> {
+        let totals = self.sorted_totals();
+        if totals.is_empty() {
+            return None;
+        }
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * totals.len() as f64).ceil() as usize;
+        Some(totals[rank.max(1) - 1])
+    }
+
+    /// One line per elf: `index,total,items` with the items separated by `;`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("elf,total,items\n");
+        for elf in self.elves.iter() {
+            let items: Vec<String> = elf.items.iter().map(|item| item.to_string()).collect();
+            writeln!(csv, "{},{},{}", elf.index, elf.total, items.join(";")).unwrap();
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.elves)?)
+    }
+
+    fn sorted_totals(&self) -> Vec<usize> {
+        let mut totals: Vec<usize> = self.elves.iter().map(|elf| elf.total).collect();
+        totals.sort_unstable();
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    #[test]
+    fn inventory() {
+        let inventory = Inventory::from_str(DATA).unwrap();
+        let top: Vec<(usize, usize)> = inventory
+            .top_k(3)
+            .iter()
+            .map(|elf| (elf.index, elf.total))
+            .collect();
+        assert_eq!(top, vec![(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(inventory.top_k(10).len(), 5);
+        assert_eq!(inventory.rank(0), Some(4));
+        assert_eq!(inventory.rank(5), None);
+        assert_eq!(inventory.elf(2).unwrap().items, vec![5000, 6000]);
+        assert_eq!(inventory.median(), Some(10000.0));
+        assert_eq!(inventory.percentile(20.0), Some(4000));
+        assert_eq!(inventory.percentile(100.0), Some(24000));
+        assert!(inventory
+            .to_csv()
+            .starts_with("elf,total,items\n0,6000,1000;2000;3000\n1,4000,4000\n"));
+        assert!(inventory
+            .to_json()
+            .unwrap()
+            .starts_with(r#"[{"index":0,"items":[1000,2000,3000],"total":6000},"#));
+    }
+
+    #[test]
+    fn zero_calories_and_last_elf() {
+        // An elf carrying nothing worth anything is still an elf, and the input does not end
+        // with a blank line
+        let inventory = Inventory::from_str("0\n\n100\n200\n\n0\n0\n\n300").unwrap();
+        let totals: Vec<(usize, usize)> = inventory
+            .elves()
+            .iter()
+            .map(|elf| (elf.index, elf.total))
+            .collect();
+        assert_eq!(totals, vec![(0, 0), (1, 300), (2, 0), (3, 300)]);
+        assert_eq!(inventory.elf(2).unwrap().items, vec![0, 0]);
+        assert_eq!(inventory.rank(0), Some(3));
+    }
+}
@@ -1,38 +1,65 @@
-use anyhow::Result;
-use std::fs;
+mod inventory;
+
+use anyhow::{anyhow, Result};
+use inventory::Inventory;
+use std::{fs, str::FromStr};
 
 fn main() -> Result<()> {
+    // `cargo run -p day1 -- --csv` (or `--json`) exports what every elf carries, `--report`
+    // tells which elves carry the most
+    if std::env::args().any(|arg| arg == "--csv") {
+        print!("{}", inventory()?.to_csv());
+        return Ok(());
+    }
+    if std::env::args().any(|arg| arg == "--json") {
+        println!("{}", inventory()?.to_json()?);
+        return Ok(());
+    }
+    if std::env::args().any(|arg| arg == "--report") {
+        print!("{}", report()?);
+        return Ok(());
+    }
     println!("First part : {}", first()?);
     println!("Second part : {}", second()?);
     Ok(())
 }
 
 fn first() -> Result<String> {
-    let ans = elf_calories()?.into_iter().max().unwrap();
-    Ok(ans.to_string())
+    let inventory = inventory()?;
+    let richest = inventory
+        .top_k(1)
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow!("no elves"))?;
+    Ok(richest.total.to_string())
 }
 
 fn second() -> Result<String> {
-    let mut calories = elf_calories()?;
-    calories.sort();
-    let ans: usize = calories.iter().rev().take(3).sum();
+    let ans: usize = inventory()?.top_k(3).iter().map(|elf| elf.total).sum();
     Ok(ans.to_string())
 }
 
-fn elf_calories() -> Result<Vec<usize>> {
+fn inventory() -> Result<Inventory> {
     let data = fs::read_to_string("day1/data/day1.txt")?;
-    Ok(data
-        .lines()
-        .scan(0_usize, |acc, line| {
-            if let Ok(calories) = line.parse::<usize>() {
-                *acc += calories;
-                Some(0)
-            } else {
-                let total = *acc;
-                *acc = 0;
-                Some(total)
-            }
-        })
-        .filter(|&x| x != 0)
-        .collect())
+    Inventory::from_str(&data)
+}
+
+fn report() -> Result<String> {
+    let inventory = inventory()?;
+    let mut report = format!(
+        "{} elves, median {}, 90th percentile {}\n",
+        inventory.elves().len(),
+        inventory.median().unwrap_or_default(),
+        inventory.percentile(90.0).unwrap_or_default(),
+    );
+    for elf in inventory.top_k(3) {
+        report += &format!(
+            "#{} elf {} : {} calories in {} items\n",
+            inventory.rank(elf.index).unwrap(),
+            elf.index,
+            elf.total,
+            elf.items.len()
+        );
+    }
+    Ok(report)
 }
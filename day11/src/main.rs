@@ -1,8 +1,8 @@
 use std::str::FromStr;
 
 use anyhow::{anyhow, Error, Result};
+use aoc_utils::records::records;
 use itertools::Itertools;
-use num::Integer;
 use std::collections::VecDeque;
 
 fn main() -> Result<()> {
@@ -13,14 +13,18 @@ fn main() -> Result<()> {
 
 fn first() -> Result<String> {
     let data = std::fs::read_to_string("day11/data/day11.txt")?;
-    let monkeys: Result<Vec<Monkey>> = data.split("\n\n").map(Monkey::from_str).collect();
+    let monkeys: Result<Vec<Monkey>> = records(&data)
+        .map(|lines| Monkey::from_str(&lines.join("\n")))
+        .collect();
     let ans = run(20, monkeys?, true);
     Ok(ans.to_string())
 }
 
 fn second() -> Result<String> {
     let data = std::fs::read_to_string("day11/data/day11.txt")?;
-    let monkeys: Result<Vec<Monkey>> = data.split("\n\n").map(Monkey::from_str).collect();
+    let monkeys: Result<Vec<Monkey>> = records(&data)
+        .map(|lines| Monkey::from_str(&lines.join("\n")))
+        .collect();
     let ans = run(10000, monkeys?, false);
     Ok(ans.to_string())
 }
@@ -190,7 +194,7 @@ fn get_value(old: usize, value: &Value) -> usize {
     }
 }
 fn test_worry_level(worry_level: usize, test: &Test) -> bool {
-    worry_level.is_multiple_of(&test.divisible_by)
+    worry_level.is_multiple_of(test.divisible_by)
 }
 fn get_next_monkey_id(test_result: bool, monkey: &Monkey) -> usize {
     if test_result {
@@ -1,11 +1,19 @@
 use anyhow::Result;
 use aoc_utils::graph::{
-    dgraph::{Dgraph, Edge},
+    bidirectional::BidirectionalSearch,
+    csr::CsrGraph,
+    dgraph::Edge,
+    export::{DotOptions, ToDot},
     shortest_path::ShortestPath,
 };
 use itertools::Itertools;
 
 fn main() -> Result<()> {
+    // `cargo run -p day12 -- --dot | dot -Tsvg > day12.svg` renders the hill with the best route
+    if std::env::args().any(|arg| arg == "--dot") {
+        println!("{}", dot()?);
+        return Ok(());
+    }
     println!("First part : {}", first()?);
     println!("Second part : {}", second()?);
     Ok(())
@@ -19,11 +27,14 @@ fn first() -> Result<String> {
         .find(|position| grid[position.y][position.x] == 'S')
         .unwrap();
     let end = get_end_position(&grid);
-    let graph = build_graph(&grid);
-    let ans = graph.disjktra_shortest_path(
-        get_vertex_id_from_position(starting_position, &grid),
-        get_vertex_id_from_position(end, &grid),
-    );
+    let graph = build_graph(&grid)?;
+    let ans = graph
+        .bidirectional_bfs(
+            get_vertex_id_from_position(starting_position, &grid),
+            get_vertex_id_from_position(end, &grid),
+        )
+        .unwrap()
+        .cost;
     Ok(ans.to_string())
 }
 
@@ -32,7 +43,7 @@ fn second() -> Result<String> {
     let grid = make_grid("day12/data/day12.txt")?;
     let starting_position = get_starting_positions(&grid);
     let end = get_end_position(&grid);
-    let graph = build_graph(&grid);
+    let graph = build_graph(&grid)?;
     let ans = starting_position
         .iter()
         .map(|start| {
@@ -46,6 +57,29 @@ fn second() -> Result<String> {
     Ok(ans.to_string())
 }
 
+fn dot() -> Result<String> {
+    let grid = make_grid("day12/data/day12.txt")?;
+    let start = get_starting_positions(&grid)
+        .into_iter()
+        .find(|position| grid[position.y][position.x] == 'S')
+        .unwrap();
+    let end = get_end_position(&grid);
+    let graph = build_graph(&grid)?;
+    let path = graph
+        .disjktra_path(
+            get_vertex_id_from_position(start, &grid),
+            get_vertex_id_from_position(end, &grid),
+        )
+        .unwrap_or_default();
+    let x_max = grid[0].len();
+    let labels = |id: usize| grid[id / x_max][id % x_max].to_string();
+    Ok(graph.to_dot(&DotOptions {
+        labels: Some(&labels),
+        weights: false,
+        highlight: &path,
+    }))
+}
+
 #[derive(Debug, Clone, Copy)]
 struct Position {
     x: usize,
@@ -53,11 +87,9 @@ struct Position {
 }
 type Grid = Vec<Vec<char>>;
 
-fn build_graph(grid: &Grid) -> Dgraph {
+fn build_graph(grid: &Grid) -> Result<CsrGraph> {
     let nb_vertices = grid.len() * grid[0].len();
-    let mut graph = Dgraph::new(nb_vertices);
-    connect_edges(&mut graph, grid);
-    graph
+    CsrGraph::from_edges(nb_vertices, connect_edges(grid))
 }
 
 fn make_grid(file_path: &str) -> Result<Grid> {
@@ -93,24 +125,26 @@ fn get_end_position(grid: &Grid) -> Position {
     }
     end
 }
-fn connect_edges(graph: &mut Dgraph, grid: &Grid) {
+fn connect_edges(grid: &Grid) -> Vec<Edge> {
+    let mut edges = Vec::new();
     for (y, line) in grid.iter().enumerate() {
         for (x, _ch) in line.iter().enumerate() {
             let current_position = Position { x, y };
             if let Some(next_position) = next_position_up(grid, current_position) {
-                graph.add_edge(make_edge(current_position, next_position, grid));
+                edges.push(make_edge(current_position, next_position, grid));
             }
             if let Some(next_position) = next_position_down(grid, current_position) {
-                graph.add_edge(make_edge(current_position, next_position, grid));
+                edges.push(make_edge(current_position, next_position, grid));
             }
             if let Some(next_position) = next_position_right(grid, current_position) {
-                graph.add_edge(make_edge(current_position, next_position, grid));
+                edges.push(make_edge(current_position, next_position, grid));
             }
             if let Some(next_position) = next_position_left(grid, current_position) {
-                graph.add_edge(make_edge(current_position, next_position, grid));
+                edges.push(make_edge(current_position, next_position, grid));
             }
         }
     }
+    edges
 }
 fn make_edge(current_position: Position, next_position: Position, grid: &Grid) -> Edge {
     Edge {
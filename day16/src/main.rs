@@ -1,6 +1,12 @@
 mod defs;
 
 fn main() -> anyhow::Result<()> {
+    // `cargo run -p day16 -- --dot | dot -Tsvg > day16.svg` renders the valves and tunnels
+    if std::env::args().any(|arg| arg == "--dot") {
+        let data = std::fs::read_to_string("day16/data/day16.txt")?;
+        println!("{}", defs::Solve::new(&data).to_dot());
+        return Ok(());
+    }
     println!("First part : {}", first()?);
     println!("Second part : {}", second()?);
     Ok(())
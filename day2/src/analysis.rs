@@ -0,0 +1,141 @@
+use crate::game::{Outcome, Rules};
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+
+/// Strategy guide where the meaning of the second column is unknown. Each round holds the move
+/// of the opponent (`A` is the first move) and the symbol of the second column (`X` is 0).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyGuide {
+    rounds: Vec<(usize, usize)>,
+}
+
+impl StrategyGuide {
+    /// Both columns must stay within the moves of `rules`.
+    pub fn parse(data: &str, rules: &Rules) -> Result<Self> {
+        let symbol = |s: &str, first_symbol: char| match s.chars().next() {
+            Some(c) if s.len() == 1 && c >= first_symbol => {
+                let id = c as usize - first_symbol as usize;
+                (id < rules.nb_moves()).then_some(id)
+            }
+            _ => None,
+        };
+        let rounds = data
+            .lines()
+            .map(|line| {
+                line.split_once(' ')
+                    .and_then(|(opponent, you)| Some((symbol(opponent, 'A')?, symbol(you, 'X')?)))
+                    .ok_or_else(|| anyhow!("Bad strategy guide line {line}"))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { rounds })
+    }
+
+    /// Total score when symbol `i` of the second column stands for move `mapping[i]`.
+    pub fn score(&self, rules: &Rules, mapping: &[usize]) -> usize {
+        self.rounds
+            .iter()
+            .map(|&(opponent, symbol)| rules.score(mapping[symbol], opponent))
+            .sum()
+    }
+
+    /// Total score when the second column is the outcome to reach: `X` to lose, `Y` to draw and
+    /// `Z` to win.
+    pub fn score_with_outcomes(&self, rules: &Rules) -> Result<usize> {
+        const OUTCOMES: [Outcome; 3] = [Outcome::Lost, Outcome::Draw, Outcome::Won];
+        self.rounds
+            .iter()
+            .map(|&(opponent, symbol)| {
+                let outcome = *OUTCOMES
+                    .get(symbol)
+                    .ok_or_else(|| anyhow!("Symbol {symbol} is not an outcome"))?;
+                let you = rules
+                    .moves_for_outcome(opponent, outcome)
+                    .next()
+                    .ok_or_else(|| anyhow!("No move reaches {outcome:?}"))?;
+                Ok(rules.score(you, opponent))
+            })
+            .sum()
+    }
+
+    /// Score of a player picking the best move every round, whatever the guide says.
+    pub fn best_possible_score(&self, rules: &Rules) -> usize {
+        self.rounds
+            .iter()
+            .map(|&(opponent, _)| {
+                (0..rules.nb_moves())
+                    .map(|you| rules.score(you, opponent))
+                    .max()
+                    .unwrap()
+            })
+            .sum()
+    }
+
+    /// Score under every one-to-one mapping of the symbols to moves, the best first.
+    pub fn bijection_scores(&self, rules: &Rules) -> Vec<(Vec<usize>, usize)> {
+        let nb_moves = rules.nb_moves();
+        let mut scores: Vec<(Vec<usize>, usize)> = (0..nb_moves)
+            .permutations(nb_moves)
+            .map(|mapping| {
+                let score = self.score(rules, &mapping);
+                (mapping, score)
+            })
+            .collect();
+        scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        scores
+    }
+
+    /// Expected total score following the guide under `mapping`, when the opponent ignores the
+    /// first column and plays move `i` with probability `distribution[i]`.
+    pub fn expected_score(&self, rules: &Rules, mapping: &[usize], distribution: &[f64]) -> f64 {
+        self.rounds
+            .iter()
+            .map(|&(_, symbol)| {
+                distribution
+                    .iter()
+                    .enumerate()
+                    .map(|(opponent, p)| p * rules.score(mapping[symbol], opponent) as f64)
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+
+    /// Frequency of each opponent move in the first column.
+    pub fn opponent_distribution(&self, rules: &Rules) -> Vec<f64> {
+        let mut counts = vec![0_usize; rules.nb_moves()];
+        for &(opponent, _) in self.rounds.iter() {
+            counts[opponent] += 1;
+        }
+        counts
+            .into_iter()
+            .map(|count| count as f64 / self.rounds.len().max(1) as f64)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analyze_guide() {
+        let rules = Rules::rock_paper_scissors();
+        let guide = StrategyGuide::parse("A Y\nB X\nC Z", &rules).unwrap();
+        assert_eq!(guide.score(&rules, &[0, 1, 2]), 15);
+        assert_eq!(guide.score_with_outcomes(&rules).unwrap(), 12);
+        assert_eq!(guide.best_possible_score(&rules), 8 + 9 + 7);
+
+        let scores = guide.bijection_scores(&rules);
+        assert_eq!(scores.len(), 6);
+        assert_eq!(scores[0], (vec![2, 1, 0], 24));
+        assert!(scores.contains(&(vec![0, 1, 2], 15)));
+
+        let uniform = [1.0 / 3.0; 3];
+        assert_eq!(guide.opponent_distribution(&rules), uniform.to_vec());
+        // Every move scores its shape plus 3 on average against a uniform opponent
+        let expected = guide.expected_score(&rules, &[0, 1, 2], &uniform);
+        assert!((expected - 15.0).abs() < 1e-9);
+
+        assert!(StrategyGuide::parse("A W", &rules).is_err());
+        assert!(StrategyGuide::parse("D X", &rules).is_err());
+    }
+}
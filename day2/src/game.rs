@@ -0,0 +1,243 @@
+use anyhow::{anyhow, Error, Result};
+use std::{collections::HashMap, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Lost,
+    Draw,
+    Won,
+}
+
+/// Rules of a game where two players each pick a move and the result only depends on the pair
+/// of moves. Moves are identified by their index in the rule table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    names: Vec<String>,
+    shape_scores: Vec<usize>,
+    /// `beats[a][b]` is true when move `a` wins against move `b`
+    beats: Vec<Vec<bool>>,
+    /// Points for a loss, a draw and a win
+    outcome_scores: [usize; 3],
+}
+
+impl Rules {
+    /// Checks that the rules are consistent: an odd number of distinct moves, where every pair
+    /// of different moves has exactly one winner and every move beats half of the others.
+    pub fn new(
+        names: Vec<String>,
+        shape_scores: Vec<usize>,
+        beats: Vec<Vec<bool>>,
+        outcome_scores: [usize; 3],
+    ) -> Result<Self> {
+        let nb_moves = names.len();
+        if nb_moves.is_multiple_of(2) {
+            return Err(anyhow!(
+                "A fair game needs an odd number of moves, got {nb_moves}"
+            ));
+        }
+        if shape_scores.len() != nb_moves
+            || beats.len() != nb_moves
+            || beats.iter().any(|row| row.len() != nb_moves)
+        {
+            return Err(anyhow!(
+                "Every move needs a shape score and a row of the table"
+            ));
+        }
+        for (a, name) in names.iter().enumerate() {
+            if names[..a].contains(name) {
+                return Err(anyhow!("Move {name} is declared twice"));
+            }
+            if beats[a][a] {
+                return Err(anyhow!("{name} cannot beat itself"));
+            }
+            for b in 0..nb_moves {
+                if a != b && beats[a][b] == beats[b][a] {
+                    return Err(anyhow!(
+                        "Exactly one of {name} and {} must beat the other",
+                        names[b]
+                    ));
+                }
+            }
+            let nb_beaten = beats[a].iter().filter(|&&beaten| beaten).count();
+            if nb_beaten != nb_moves / 2 {
+                return Err(anyhow!(
+                    "{name} beats {nb_beaten} moves instead of {}",
+                    nb_moves / 2
+                ));
+            }
+        }
+        Ok(Self {
+            names,
+            shape_scores,
+            beats,
+            outcome_scores,
+        })
+    }
+
+    /// Game where move `i` beats move `i - d` (modulo the number of moves) for every odd `d`.
+    pub fn cyclic(
+        names: &[&str],
+        shape_scores: &[usize],
+        outcome_scores: [usize; 3],
+    ) -> Result<Self> {
+        let nb_moves = names.len();
+        let beats = (0..nb_moves)
+            .map(|a| {
+                (0..nb_moves)
+                    .map(|b| (a + nb_moves - b) % nb_moves % 2 == 1)
+                    .collect()
+            })
+            .collect();
+        Self::new(
+            names.iter().map(|name| name.to_string()).collect(),
+            shape_scores.to_vec(),
+            beats,
+            outcome_scores,
+        )
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Self::cyclic(&["Rock", "Paper", "Scissors"], &[1, 2, 3], [0, 3, 6]).unwrap()
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::cyclic(
+            &["Rock", "Paper", "Scissors", "Spock", "Lizard"],
+            &[1, 2, 3, 4, 5],
+            [0, 3, 6],
+        )
+        .unwrap()
+    }
+
+    pub fn nb_moves(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, move_id: usize) -> &str {
+        &self.names[move_id]
+    }
+
+    pub fn outcome(&self, you: usize, opponent: usize) -> Outcome {
+        if self.beats[you][opponent] {
+            Outcome::Won
+        } else if self.beats[opponent][you] {
+            Outcome::Lost
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn score(&self, you: usize, opponent: usize) -> usize {
+        let outcome_score = match self.outcome(you, opponent) {
+            Outcome::Lost => self.outcome_scores[0],
+            Outcome::Draw => self.outcome_scores[1],
+            Outcome::Won => self.outcome_scores[2],
+        };
+        self.shape_scores[you] + outcome_score
+    }
+
+    /// Moves leading to `outcome` against `opponent`, by index.
+    pub fn moves_for_outcome(
+        &self,
+        opponent: usize,
+        outcome: Outcome,
+    ) -> impl Iterator<Item = usize> + '_ {
+        (0..self.nb_moves()).filter(move |&you| self.outcome(you, opponent) == outcome)
+    }
+}
+
+/// Rule table with one line giving the points for a loss, a draw and a win, then one line per
+/// move: its name, its shape score and the moves it beats.
+///
+/// ```text
+/// outcomes 0 3 6
+/// Rock 1 beats Scissors
+/// Paper 2 beats Rock
+/// Scissors 3 beats Paper
+/// ```
+impl FromStr for Rules {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().filter(|line| !line.trim().is_empty());
+        let outcome_scores: Vec<usize> = lines
+            .next()
+            .and_then(|line| line.trim().strip_prefix("outcomes "))
+            .ok_or_else(|| anyhow!("The rule table must start with the outcome scores"))?
+            .split_whitespace()
+            .map(|score| score.parse())
+            .collect::<Result<_, _>>()?;
+        let outcome_scores: [usize; 3] = outcome_scores
+            .try_into()
+            .map_err(|_| anyhow!("Expected 3 outcome scores"))?;
+
+        let mut names = Vec::new();
+        let mut shape_scores = Vec::new();
+        let mut beaten_names = Vec::new();
+        for line in lines {
+            let mut items = line.split_whitespace();
+            let name = items.next().unwrap();
+            let shape_score = items
+                .next()
+                .ok_or_else(|| anyhow!("Missing shape score for {name}"))?
+                .parse()?;
+            if items.next() != Some("beats") {
+                return Err(anyhow!("Expected `{name} <score> beats <moves>`"));
+            }
+            names.push(name.to_string());
+            shape_scores.push(shape_score);
+            beaten_names.push(items.collect::<Vec<_>>());
+        }
+
+        let ids: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (name.as_str(), id))
+            .collect();
+        let mut beats = vec![vec![false; names.len()]; names.len()];
+        for (a, beaten) in beaten_names.iter().enumerate() {
+            for name in beaten {
+                let b = ids
+                    .get(name)
+                    .ok_or_else(|| anyhow!("Unknown move {name}"))?;
+                beats[a][*b] = true;
+            }
+        }
+        Self::new(names, shape_scores, beats, outcome_scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_tables() {
+        let rules = Rules::rock_paper_scissors();
+        let table = "outcomes 0 3 6
+            Rock 1 beats Scissors
+            Paper 2 beats Rock
+            Scissors 3 beats Paper";
+        assert_eq!(Rules::from_str(table).unwrap(), rules);
+        assert_eq!(rules.score(1, 0), 8);
+        assert_eq!(rules.score(0, 1), 1);
+        assert_eq!(rules.score(2, 2), 6);
+        assert_eq!(
+            rules
+                .moves_for_outcome(0, Outcome::Lost)
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+
+        let rpsls = Rules::rock_paper_scissors_lizard_spock();
+        let lizard = 4;
+        assert_eq!(rpsls.outcome(lizard, 3), Outcome::Won); // Lizard poisons Spock
+        assert_eq!(rpsls.outcome(lizard, 1), Outcome::Won); // Lizard eats Paper
+        assert_eq!(rpsls.outcome(lizard, 0), Outcome::Lost); // Rock crushes Lizard
+        assert_eq!(rpsls.outcome(lizard, 2), Outcome::Lost); // Scissors decapitate Lizard
+        assert_eq!(rpsls.moves_for_outcome(3, Outcome::Won).count(), 2);
+
+        assert!(Rules::cyclic(&["Rock", "Paper"], &[1, 2], [0, 3, 6]).is_err());
+        assert!(Rules::from_str(&table.replace("Paper 2 beats Rock", "Paper 2 beats")).is_err());
+        assert!(Rules::from_str(&table.replace("beats Paper", "beats Rock")).is_err());
+    }
+}
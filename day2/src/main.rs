@@ -1,163 +1,91 @@
-use anyhow::{anyhow, Error, Ok, Result};
+mod analysis;
+mod game;
+
+use analysis::StrategyGuide;
+use anyhow::{anyhow, Result};
+use game::Rules;
+use itertools::Itertools;
 use std::{fs, str::FromStr};
 
 fn main() -> Result<()> {
-    println!("First part : {}", first()?);
-    println!("Second part : {}", second()?);
+    let rules = rules_from_args()?;
+    // `cargo run -p day2 -- --analyze [--distribution 0.5,0.3,0.2]`
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|arg| arg == "--analyze") {
+        let distribution = match args.iter().position(|arg| arg == "--distribution") {
+            Some(i) => Some(
+                args.get(i + 1)
+                    .ok_or_else(|| anyhow!("--distribution expects probabilities"))?
+                    .split(',')
+                    .map(|p| p.parse())
+                    .collect::<Result<_, _>>()?,
+            ),
+            None => None,
+        };
+        print!("{}", analyze(&rules, distribution)?);
+        return Ok(());
+    }
+    println!("First part : {}", first(&rules)?);
+    println!("Second part : {}", second(&rules)?);
     Ok(())
 }
 
-fn first() -> Result<String> {
-    let data = fs::read_to_string("day2/data/day2.txt")?;
-    let total_score: usize = data
-        .lines()
-        .map(|line| {
-            let score: usize = Round::from_str(line).unwrap().into();
-            score
-        })
-        .sum();
-    Ok(total_score.to_string())
-}
-
-fn second() -> Result<String> {
-    let data = fs::read_to_string("day2/data/day2.txt")?;
-    let total_score: usize = data
-        .lines()
-        .map(|line| {
-            let score: usize = RoundWithOutcomeGiven::from_str(line).unwrap().into();
-            score
-        })
-        .sum();
-    Ok(total_score.to_string())
-}
-
-#[derive(Debug, Clone)]
-enum Choice {
-    Rock,
-    Paper,
-    Scissors,
-}
-impl From<Choice> for usize {
-    fn from(choice: Choice) -> Self {
-        match choice {
-            Choice::Rock => 1,
-            Choice::Paper => 2,
-            Choice::Scissors => 3,
-        }
+/// Rock paper scissors unless `--rpsls` or `--rules <rule table file>` is given.
+fn rules_from_args() -> Result<Rules> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|arg| arg == "--rpsls") {
+        return Ok(Rules::rock_paper_scissors_lizard_spock());
     }
-}
-impl FromStr for Choice {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "A" | "X" => Ok(Self::Rock),
-            "B" | "Y" => Ok(Self::Paper),
-            "C" | "Z" => Ok(Self::Scissors),
-            _ => Err(anyhow!("Bad Rock Paper Scissor choice")),
-        }
-    }
-}
-impl From<RoundWithOutcomeGiven> for Choice {
-    fn from(round: RoundWithOutcomeGiven) -> Self {
-        match (round.opponent_choice, round.round_outcome) {
-            (Self::Rock, RoundOutcome::Lost) => Self::Scissors,
-            (Self::Rock, RoundOutcome::Draw) => Self::Rock,
-            (Self::Rock, RoundOutcome::Won) => Self::Paper,
-            (Self::Paper, RoundOutcome::Lost) => Self::Rock,
-            (Self::Paper, RoundOutcome::Draw) => Self::Paper,
-            (Self::Paper, RoundOutcome::Won) => Self::Scissors,
-            (Self::Scissors, RoundOutcome::Lost) => Self::Paper,
-            (Self::Scissors, RoundOutcome::Draw) => Self::Scissors,
-            (Self::Scissors, RoundOutcome::Won) => Self::Rock,
+    match args.iter().position(|arg| arg == "--rules") {
+        Some(i) => {
+            let path = args
+                .get(i + 1)
+                .ok_or_else(|| anyhow!("--rules expects a file"))?;
+            Rules::from_str(&fs::read_to_string(path)?)
         }
+        None => Ok(Rules::rock_paper_scissors()),
     }
 }
 
-#[derive(Clone, Debug)]
-enum RoundOutcome {
-    Lost,
-    Draw,
-    Won,
-}
-impl From<RoundOutcome> for usize {
-    fn from(outcome: RoundOutcome) -> Self {
-        match outcome {
-            RoundOutcome::Lost => 0,
-            RoundOutcome::Draw => 3,
-            RoundOutcome::Won => 6,
-        }
-    }
-}
-impl From<Round> for RoundOutcome {
-    fn from(round: Round) -> Self {
-        match (round.you, round.opponent) {
-            (Choice::Rock, Choice::Rock) => Self::Draw,
-            (Choice::Rock, Choice::Paper) => Self::Lost,
-            (Choice::Rock, Choice::Scissors) => Self::Won,
-            (Choice::Paper, Choice::Rock) => Self::Won,
-            (Choice::Paper, Choice::Paper) => Self::Draw,
-            (Choice::Paper, Choice::Scissors) => Self::Lost,
-            (Choice::Scissors, Choice::Rock) => Self::Lost,
-            (Choice::Scissors, Choice::Paper) => Self::Won,
-            (Choice::Scissors, Choice::Scissors) => Self::Draw,
-        }
-    }
-}
-impl FromStr for RoundOutcome {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "X" => Ok(Self::Lost),
-            "Y" => Ok(Self::Draw),
-            "Z" => Ok(Self::Won),
-            _ => Err(anyhow!("Wrong round outcome")),
-        }
-    }
+fn first(rules: &Rules) -> Result<String> {
+    let guide = strategy_guide(rules)?;
+    let identity: Vec<usize> = (0..rules.nb_moves()).collect();
+    Ok(guide.score(rules, &identity).to_string())
 }
 
-#[derive(Debug, Clone)]
-struct Round {
-    you: Choice,
-    opponent: Choice,
+fn second(rules: &Rules) -> Result<String> {
+    let guide = strategy_guide(rules)?;
+    Ok(guide.score_with_outcomes(rules)?.to_string())
 }
-impl FromStr for Round {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.split_whitespace();
-        let opponent = Choice::from_str(s.next().unwrap())?;
-        let you = Choice::from_str(s.next().unwrap())?;
-        Ok(Self { opponent, you })
+
+/// How plausible each reading of the second column is. The opponent distribution defaults to
+/// the frequencies of the first column.
+fn analyze(rules: &Rules, distribution: Option<Vec<f64>>) -> Result<String> {
+    let guide = strategy_guide(rules)?;
+    let distribution = distribution.unwrap_or_else(|| guide.opponent_distribution(rules));
+    if distribution.len() != rules.nb_moves() {
+        return Err(anyhow!("Expected one probability per move"));
     }
-}
-impl From<Round> for usize {
-    fn from(round: Round) -> Self {
-        let round_outcome: usize = RoundOutcome::from(round.clone()).into();
-        let choice_value: usize = round.you.into();
-        round_outcome + choice_value
+    let mut report = format!(
+        "Best possible score : {}\nOutcome reading : {}\n",
+        guide.best_possible_score(rules),
+        guide.score_with_outcomes(rules)?
+    );
+    for (mapping, score) in guide.bijection_scores(rules) {
+        let moves = mapping
+            .iter()
+            .enumerate()
+            .map(|(symbol, &move_id)| {
+                format!("{}={}", (b'X' + symbol as u8) as char, rules.name(move_id))
+            })
+            .join(" ");
+        let expected = guide.expected_score(rules, &mapping, &distribution);
+        report += &format!("{moves} : {score} (expected {expected:.1})\n");
     }
+    Ok(report)
 }
 
-#[derive(Debug, Clone)]
-struct RoundWithOutcomeGiven {
-    round_outcome: RoundOutcome,
-    opponent_choice: Choice,
-}
-impl FromStr for RoundWithOutcomeGiven {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.split_whitespace();
-        let opponent_choice = Choice::from_str(s.next().unwrap())?;
-        let round_outcome = RoundOutcome::from_str(s.next().unwrap())?;
-        Ok(Self {
-            round_outcome,
-            opponent_choice,
-        })
-    }
-}
-impl From<RoundWithOutcomeGiven> for usize {
-    fn from(round: RoundWithOutcomeGiven) -> Self {
-        let choice_value: usize = Choice::from(round.clone()).into();
-        let round_outcome: usize = round.round_outcome.into();
-        choice_value + round_outcome
-    }
+fn strategy_guide(rules: &Rules) -> Result<StrategyGuide> {
+    let data = fs::read_to_string("day2/data/day2.txt")?;
+    StrategyGuide::parse(&data, rules)
 }
@@ -0,0 +1,135 @@
+use crate::rucksack::{ItemSet, Rucksack};
+use anyhow::{anyhow, Result};
+
+/// Outcome of the search for groups sharing exactly one badge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BadgeGroups {
+    /// The only partition, each group listing rucksack indices in increasing order
+    Unique(Vec<Vec<usize>>),
+    /// One of several partitions
+    Multiple(Vec<Vec<usize>>),
+    Impossible,
+}
+
+/// Partitions rucksacks listed in any order into groups of `group_size` where the rucksacks of
+/// each group have exactly one item in common.
+pub fn find_badge_groups(rucksacks: &[Rucksack], group_size: usize) -> Result<BadgeGroups> {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return Err(anyhow!(
+            "{} rucksacks cannot be split into groups of {group_size}",
+            rucksacks.len()
+        ));
+    }
+    let mut search = Search {
+        items: rucksacks.iter().map(Rucksack::items).collect(),
+        group_size,
+        assigned: vec![false; rucksacks.len()],
+        groups: Vec::new(),
+        first_solution: None,
+        nb_solutions: 0,
+    };
+    search.partition();
+    Ok(match (search.first_solution, search.nb_solutions) {
+        (None, _) => BadgeGroups::Impossible,
+        (Some(groups), 1) => BadgeGroups::Unique(groups),
+        (Some(groups), _) => BadgeGroups::Multiple(groups),
+    })
+}
+
+struct Search {
+    items: Vec<ItemSet>,
+    group_size: usize,
+    assigned: Vec<bool>,
+    groups: Vec<Vec<usize>>,
+    first_solution: Option<Vec<Vec<usize>>>,
+    nb_solutions: usize,
+}
+
+impl Search {
+    /// Stops as soon as a second partition proves the first one is not unique.
+    fn partition(&mut self) {
+        if self.nb_solutions > 1 {
+            return;
+        }
+        // The first rucksack without a group has to join one, which keeps every partition from
+        // being enumerated once per ordering of its groups
+        let Some(first) = self.assigned.iter().position(|&assigned| !assigned) else {
+            self.nb_solutions += 1;
+            self.first_solution
+                .get_or_insert_with(|| self.groups.clone());
+            return;
+        };
+        self.assigned[first] = true;
+        self.extend_group(vec![first], self.items[first]);
+        self.assigned[first] = false;
+    }
+
+    fn extend_group(&mut self, group: Vec<usize>, shared: ItemSet) {
+        if group.len() == self.group_size {
+            if shared.len() == 1 {
+                self.groups.push(group);
+                self.partition();
+                self.groups.pop();
+            }
+            return;
+        }
+        let last = *group.last().unwrap();
+        for next in last + 1..self.items.len() {
+            let shared = shared.intersection(self.items[next]);
+            if self.assigned[next] || shared.is_empty() {
+                continue;
+            }
+            self.assigned[next] = true;
+            let mut group = group.clone();
+            group.push(next);
+            self.extend_group(group, shared);
+            self.assigned[next] = false;
+            if self.nb_solutions > 1 {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rucksacks(data: &[&str]) -> Vec<Rucksack> {
+        data.iter().map(|line| line.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn badge_groups() {
+        // The two groups of the example, shuffled. Other partitions exist, but not first
+        let unordered = rucksacks(&[
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "ttgJtRGJQctTZtZT",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+            "PmmdzqPrVvPwwTWBwg",
+        ]);
+        assert_eq!(
+            find_badge_groups(&unordered, 3).unwrap(),
+            BadgeGroups::Multiple(vec![vec![0, 2, 4], vec![1, 3, 5]])
+        );
+        assert_eq!(
+            find_badge_groups(&rucksacks(&["ax", "by", "ay", "bz", "bx", "az"]), 3).unwrap(),
+            BadgeGroups::Unique(vec![vec![0, 2, 5], vec![1, 3, 4]])
+        );
+        assert_eq!(
+            find_badge_groups(&rucksacks(&["ab", "ac", "ad", "bc", "bd", "cd"]), 3).unwrap(),
+            BadgeGroups::Impossible
+        );
+        assert!(matches!(
+            find_badge_groups(
+                &rucksacks(&["a", "a", "a", "b", "b", "b", "ab", "ab", "ab"]),
+                3
+            )
+            .unwrap(),
+            BadgeGroups::Multiple(_)
+        ));
+        assert!(find_badge_groups(&unordered, 4).is_err());
+    }
+}
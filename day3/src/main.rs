@@ -1,78 +1,64 @@
-use anyhow::Result;
-use std::collections::HashSet;
+mod badges;
+mod rucksack;
+
+use anyhow::{anyhow, Result};
+use badges::{find_badge_groups, BadgeGroups};
+use rucksack::{group_badges, ItemSet, Rucksack};
 use std::fs;
 
 fn main() -> Result<()> {
+    // `cargo run -p day3 -- --group-size 6` to share badges across larger groups, `--unordered`
+    // to find the groups when they are not on consecutive lines
+    let args: Vec<String> = std::env::args().collect();
+    let group_size = match args.iter().position(|arg| arg == "--group-size") {
+        Some(i) => args
+            .get(i + 1)
+            .ok_or_else(|| anyhow!("--group-size expects a number"))?
+            .parse()?,
+        None => 3,
+    };
+    if args.iter().any(|arg| arg == "--unordered") {
+        println!("{}", unordered(group_size)?);
+        return Ok(());
+    }
     println!("First part : {}", first()?);
-    println!("Second part : {}", second()?);
+    println!("Second part : {}", second(group_size)?);
     Ok(())
 }
 
 fn first() -> Result<String> {
-    let data = fs::read_to_string("day3/data/day3.txt")?;
-    let ans: usize = data
-        .lines()
-        .map(|line| {
-            let compartments = split_in_two_compartments(line);
-            let items_in_both = get_items_appearing_on_both(compartments);
-            get_items_priority_total(&items_in_both)
-        })
-        .sum();
+    let ans = rucksacks()?
+        .iter()
+        .map(|rucksack| Ok(rucksack.shared_by_compartments(2)?.priority_total()))
+        .sum::<Result<usize>>()?;
     Ok(ans.to_string())
 }
 
-fn second() -> Result<String> {
-    let data = fs::read_to_string("day3/data/day3.txt")?;
-    let lines: Vec<&str> = data.lines().collect();
-    let ans: usize = lines
-        .chunks(3)
-        .map(|compartments| {
-            let items = get_items_appearing_on_3(compartments);
-            get_items_priority_total(&items)
-        })
+fn second(group_size: usize) -> Result<String> {
+    let ans: usize = group_badges(&rucksacks()?, group_size)?
+        .into_iter()
+        .map(|badges| badges.priority_total())
         .sum();
-
     Ok(ans.to_string())
 }
 
-fn split_in_two_compartments(line: &str) -> (Vec<char>, Vec<char>) {
-    let nb_items_per_compartments = line.chars().count() / 2;
-    let mut items = line.chars();
-    let items = items.by_ref();
-    let first_compartment = items.take(nb_items_per_compartments).collect();
-    let second_compartment = items.take(nb_items_per_compartments).collect();
-    (first_compartment, second_compartment)
-}
-
-fn get_items_appearing_on_both(compartments: (Vec<char>, Vec<char>)) -> HashSet<char> {
-    let mut items = HashSet::new();
-    for c in compartments.0.iter() {
-        if compartments.1.contains(c) {
-            items.insert(*c);
-        }
-    }
-    items
-}
-
-fn get_items_appearing_on_3(compartments: &[&str]) -> HashSet<char> {
-    let mut items = HashSet::new();
-    for c in compartments[0].chars() {
-        if compartments[1].contains(c) && compartments[2].contains(c) {
-            items.insert(c);
-        }
-    }
-    items
+fn rucksacks() -> Result<Vec<Rucksack>> {
+    let data = fs::read_to_string("day3/data/day3.txt")?;
+    data.lines().map(|line| line.parse()).collect()
 }
 
-fn get_items_priority_total(items: &HashSet<char>) -> usize {
-    items
+/// Badge groups found without assuming the groups are listed on consecutive lines.
+fn unordered(group_size: usize) -> Result<String> {
+    let rucksacks = rucksacks()?;
+    let (status, groups) = match find_badge_groups(&rucksacks, group_size)? {
+        BadgeGroups::Unique(groups) => ("Unique", groups),
+        BadgeGroups::Multiple(groups) => ("Multiple", groups),
+        BadgeGroups::Impossible => return Ok("No partition into badge groups".to_string()),
+    };
+    let badges: usize = groups
         .iter()
-        .map(|&item| {
-            if item.is_uppercase() {
-                item as usize - 38 // 'A'=65 and we rebase to 27 so.. 65-27
-            } else {
-                item as usize - 96 // 'a' = 97 and we rebase to 1 so 97-1
-            }
-        })
-        .sum()
+        .map(|group| ItemSet::intersect_all(group.iter().map(|&i| rucksacks[i].items())))
+        .map(|badge| badge.priority_total())
+        .sum();
+    Ok(format!("{status} partition, badge priorities : {badges}"))
 }
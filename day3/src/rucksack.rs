@@ -0,0 +1,162 @@
+use anyhow::{anyhow, Error, Result};
+use std::str::FromStr;
+
+/// `a` to `z` have priorities 1 to 26, `A` to `Z` 27 to 52.
+pub fn priority(item: char) -> Option<usize> {
+    match item {
+        'a'..='z' => Some(item as usize - 'a' as usize + 1),
+        'A'..='Z' => Some(item as usize - 'A' as usize + 27),
+        _ => None,
+    }
+}
+
+/// Set of items, bit `p` standing for the item of priority `p`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    /// Every item, from priority 1 to 52.
+    pub const ALL: ItemSet = ItemSet(((1 << 53) - 1) & !1);
+
+    pub fn from_priorities(priorities: impl IntoIterator<Item = usize>) -> Self {
+        Self(priorities.into_iter().fold(0, |mask, p| mask | 1 << p))
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Items found in every set. Intersecting no set at all gives `ItemSet::ALL`.
+    pub fn intersect_all(sets: impl IntoIterator<Item = Self>) -> Self {
+        sets.into_iter().fold(Self::ALL, Self::intersection)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn priorities(self) -> impl Iterator<Item = usize> {
+        (1..=52).filter(move |p| self.0 & 1 << p != 0)
+    }
+
+    pub fn priority_total(self) -> usize {
+        self.priorities().sum()
+    }
+}
+
+/// Items of a rucksack, as priorities, in the order they were listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rucksack {
+    items: Vec<usize>,
+}
+
+impl FromStr for Rucksack {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let items = s
+            .trim()
+            .chars()
+            .map(|item| priority(item).ok_or_else(|| anyhow!("Unknown item {item}")))
+            .collect::<Result<_>>()?;
+        Ok(Self { items })
+    }
+}
+
+impl Rucksack {
+    pub fn items(&self) -> ItemSet {
+        ItemSet::from_priorities(self.items.iter().copied())
+    }
+
+    /// Splits the rucksack into `nb_compartments` compartments holding the same number of items.
+    /// The compartments of an empty rucksack are all empty.
+    pub fn compartments(&self, nb_compartments: usize) -> Result<Vec<ItemSet>> {
+        if nb_compartments == 0 || !self.items.len().is_multiple_of(nb_compartments) {
+            return Err(anyhow!(
+                "{} items cannot fill {nb_compartments} compartments evenly",
+                self.items.len()
+            ));
+        }
+        if self.items.is_empty() {
+            return Ok(vec![ItemSet::default(); nb_compartments]);
+        }
+        Ok(self
+            .items
+            .chunks(self.items.len() / nb_compartments)
+            .map(|compartment| ItemSet::from_priorities(compartment.iter().copied()))
+            .collect())
+    }
+
+    /// Items found in each of the `nb_compartments` compartments.
+    pub fn shared_by_compartments(&self, nb_compartments: usize) -> Result<ItemSet> {
+        Ok(ItemSet::intersect_all(self.compartments(nb_compartments)?))
+    }
+}
+
+/// Items shared by every rucksack of each group of `group_size` consecutive rucksacks.
+pub fn group_badges(rucksacks: &[Rucksack], group_size: usize) -> Result<Vec<ItemSet>> {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return Err(anyhow!(
+            "{} rucksacks cannot be split into groups of {group_size}",
+            rucksacks.len()
+        ));
+    }
+    Ok(rucksacks
+        .chunks(group_size)
+        .map(|group| ItemSet::intersect_all(group.iter().map(Rucksack::items)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn shared_items() {
+        let rucksacks: Vec<Rucksack> = DATA.lines().map(|line| line.parse().unwrap()).collect();
+        let shared: Vec<usize> = rucksacks
+            .iter()
+            .map(|rucksack| rucksack.shared_by_compartments(2).unwrap().priority_total())
+            .collect();
+        assert_eq!(shared, vec![16, 38, 42, 22, 20, 19]);
+
+        let badges = group_badges(&rucksacks, 3).unwrap();
+        assert_eq!(
+            badges,
+            vec![
+                ItemSet::from_priorities([18]),
+                ItemSet::from_priorities([52])
+            ]
+        );
+        assert_eq!(group_badges(&rucksacks, 6).unwrap()[0], ItemSet::default());
+        assert!(group_badges(&rucksacks, 4).is_err());
+
+        let rucksack: Rucksack = "abcabcab".parse().unwrap();
+        assert_eq!(
+            rucksack.shared_by_compartments(4).unwrap(),
+            ItemSet::default()
+        );
+        assert_eq!(
+            rucksack.shared_by_compartments(1).unwrap(),
+            ItemSet::from_priorities([1, 2, 3])
+        );
+        assert!(rucksack.compartments(3).is_err());
+
+        let empty: Rucksack = "".parse().unwrap();
+        assert_eq!(empty.compartments(2).unwrap(), vec![ItemSet::default(); 2]);
+        assert_eq!(empty.shared_by_compartments(2).unwrap(), ItemSet::default());
+        assert!(empty.compartments(0).is_err());
+        assert_eq!(ItemSet::intersect_all([]).priorities().count(), 52);
+        assert!("ab1".parse::<Rucksack>().is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::section::{Pair, SectionRange};
+use std::{cmp::Reverse, fmt, iter};
+
+/// First elf (`elf` 0) or second elf (`elf` 1) of the pair at index `pair`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ElfId {
+    pub pair: usize,
+    pub elf: usize,
+}
+
+impl fmt::Display for ElfId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.pair, self.elf)
+    }
+}
+
+/// New sections for one elf of a pair, `None` when every section it had is covered by the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reassignment {
+    pub elf: ElfId,
+    pub range: Option<SectionRange>,
+}
+
+/// Assignments of every pair of the camp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Camp {
+    pairs: Vec<Pair>,
+}
+
+impl Camp {
+    pub fn new(pairs: Vec<Pair>) -> Self {
+        Self { pairs }
+    }
+
+    pub fn elves(&self) -> impl Iterator<Item = (ElfId, SectionRange)> + '_ {
+        self.pairs
+            .iter()
+            .enumerate()
+            .flat_map(|(pair, &Pair(a, b))| {
+                [(ElfId { pair, elf: 0 }, a), (ElfId { pair, elf: 1 }, b)]
+            })
+    }
+
+    /// Number of elves working on each section, sweeping over the ends of the ranges. Ranges
+    /// with the same count are merged and sections nobody covers are left out.
+    pub fn coverage(&self) -> Vec<(SectionRange, usize)> {
+        // A range opens at its first section and closes at its last one, opening first
+        let mut events: Vec<(usize, bool)> = self
+            .elves()
+            .flat_map(|(_, range)| [(range.start, false), (range.end, true)])
+            .collect();
+        events.sort_unstable();
+
+        let mut coverage: Vec<(SectionRange, usize)> = Vec::new();
+        let mut count = 0;
+        // First section whose count is not known yet
+        let mut next_start = 0;
+        for (section, closing) in events {
+            let end = if closing {
+                Some(section)
+            } else {
+                section.checked_sub(1)
+            };
+            if let Some(end) = end.filter(|&end| count > 0 && next_start <= end) {
+                let range = SectionRange {
+                    start: next_start,
+                    end,
+                };
+                match coverage.last_mut() {
+                    Some((last, last_count))
+                        if *last_count == count && last.end + 1 == range.start =>
+                    {
+                        last.end = range.end
+                    }
+                    _ => coverage.push((range, count)),
+                }
+            }
+            if closing {
+                count -= 1;
+                // Only ranges closing on the very last section can follow
+                let Some(next) = section.checked_add(1) else {
+                    break;
+                };
+                next_start = next;
+            } else {
+                count += 1;
+                next_start = section;
+            }
+        }
+        coverage
+    }
+
+    /// Most elves working on a same section, and the ranges where it happens.
+    pub fn peak_concurrency(&self) -> (usize, Vec<SectionRange>) {
+        let coverage = self.coverage();
+        let peak = coverage.iter().map(|&(_, count)| count).max().unwrap_or(0);
+        let ranges = coverage
+            .into_iter()
+            .filter(|&(_, count)| count == peak)
+            .map(|(range, _)| range)
+            .collect();
+        (peak, ranges)
+    }
+
+    /// Sections from 1 to the last assigned section that no elf works on.
+    pub fn uncovered(&self) -> Vec<SectionRange> {
+        let mut uncovered = Vec::new();
+        let mut next_start = 1;
+        for (range, _) in self.coverage() {
+            if range.start > next_start {
+                uncovered.push(SectionRange {
+                    start: next_start,
+                    end: range.start - 1,
+                });
+            }
+            next_start = range.end.saturating_add(1);
+        }
+        uncovered
+    }
+
+    /// Elves whose ranges are chained together by overlaps, the largest group first.
+    pub fn overlap_clusters(&self) -> Vec<Vec<ElfId>> {
+        let mut elves: Vec<(ElfId, SectionRange)> = self.elves().collect();
+        elves.sort_unstable_by_key(|&(elf, range)| (range.start, elf));
+
+        let mut clusters: Vec<Vec<ElfId>> = Vec::new();
+        let mut cluster_end = None;
+        for (elf, range) in elves {
+            match cluster_end {
+                Some(end) if range.start <= end => {
+                    clusters.last_mut().unwrap().push(elf);
+                    cluster_end = Some(range.end.max(end));
+                }
+                _ => {
+                    clusters.push(vec![elf]);
+                    cluster_end = Some(range.end);
+                }
+            }
+        }
+        for cluster in clusters.iter_mut() {
+            cluster.sort_unstable();
+        }
+        clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.len()));
+        clusters
+    }
+
+    pub fn largest_overlap_cluster(&self) -> Vec<ElfId> {
+        self.overlap_clusters()
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// One reassignment per overlapping pair, which is the fewest possible. The reassigned elf
+    /// keeps the longest part of its range that the other elf does not cover. The elf is picked
+    /// so that the pair loses the fewest sections, then so that it keeps the most sections.
+    pub fn reassignments(&self) -> Vec<Reassignment> {
+        self.pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, pair)| pair.overlaps())
+            .map(|(pair_id, pair)| {
+                let Pair(a, b) = *pair;
+                // Sections nobody covers once the elf paired with `other` only keeps `range`
+                let lost = |other: SectionRange, range: Option<SectionRange>| {
+                    pair.union_len() - other.len() - range.map_or(0, |range| range.len())
+                };
+                // On a tie, the second elf is the one reassigned
+                let candidates = [(0, a, b), (1, b, a)].map(|(elf, range, other)| {
+                    let kept = longest_outside(range, other);
+                    (elf, kept, lost(other, kept))
+                });
+                let (elf, range, _) = candidates
+                    .into_iter()
+                    .max_by_key(|&(_, range, lost)| {
+                        (Reverse(lost), range.map_or(0, |range| range.len()))
+                    })
+                    .unwrap();
+                Reassignment {
+                    elf: ElfId { pair: pair_id, elf },
+                    range,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Longest part of `range` outside of `other`.
+fn longest_outside(range: SectionRange, other: SectionRange) -> Option<SectionRange> {
+    let before = (range.start < other.start).then(|| SectionRange {
+        start: range.start,
+        end: range.end.min(other.start - 1),
+    });
+    let after = (range.end > other.end).then(|| SectionRange {
+        start: range.start.max(other.end + 1),
+        end: range.end,
+    });
+    iter::empty()
+        .chain(before)
+        .chain(after)
+        .max_by_key(|range| range.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: usize, end: usize) -> SectionRange {
+        SectionRange { start, end }
+    }
+
+    #[test]
+    fn camp_report() {
+        let pairs = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n12-13,15-15"
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        let camp = Camp::new(pairs);
+        assert_eq!(camp.coverage()[0], (range(2, 2), 4));
+        assert_eq!(camp.peak_concurrency(), (8, vec![range(6, 6)]));
+        assert_eq!(
+            camp.uncovered(),
+            vec![range(1, 1), range(10, 11), range(14, 14)]
+        );
+
+        let clusters = camp.overlap_clusters();
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters[0].len(), 12);
+        assert_eq!(camp.largest_overlap_cluster(), clusters[0]);
+
+        // Built by hand, parsing rejects ranges ending at `usize::MAX`
+        let max = usize::MAX;
+        let edge = Camp::new(vec![Pair(range(max - 2, max), range(max - 1, max))]);
+        assert_eq!(
+            edge.coverage(),
+            vec![(range(max - 2, max - 2), 1), (range(max - 1, max), 2)]
+        );
+        assert_eq!(edge.uncovered(), vec![range(1, max - 3)]);
+
+        let reassignments = camp.reassignments();
+        assert_eq!(
+            reassignments,
+            vec![
+                Reassignment {
+                    elf: ElfId { pair: 2, elf: 1 },
+                    range: Some(range(8, 9))
+                },
+                // Moving the first elf to 8-8 would leave section 2 to nobody
+                Reassignment {
+                    elf: ElfId { pair: 3, elf: 1 },
+                    range: None
+                },
+                Reassignment {
+                    elf: ElfId { pair: 4, elf: 1 },
+                    range: Some(range(4, 5))
+                },
+                Reassignment {
+                    elf: ElfId { pair: 5, elf: 1 },
+                    range: Some(range(7, 8))
+                },
+            ]
+        );
+    }
+}
@@ -1,53 +1,88 @@
-use anyhow::{Error, Result};
-use std::{collections::HashSet, fs, str::FromStr};
+mod camp;
+mod section;
+
+use anyhow::Result;
+use camp::Camp;
+use section::Pair;
+use std::fs;
 
 fn main() -> Result<()> {
+    // `cargo run -p day4 -- --pairs` details the overlap of every pair, `--camp` reports the
+    // conflicts across the whole camp
+    if std::env::args().any(|arg| arg == "--pairs") {
+        print!("{}", pair_details()?);
+        return Ok(());
+    }
+    if std::env::args().any(|arg| arg == "--camp") {
+        print!("{}", camp_report()?);
+        return Ok(());
+    }
     println!("First part : {}", first()?);
     println!("Second part : {}", second()?);
     Ok(())
 }
 
 fn first() -> Result<String> {
-    let data = fs::read_to_string("day4/data/day4.txt")?;
-    let ans: usize = data
-        .lines()
-        .map(|line| make_sets_from_ranges(line).unwrap())
-        .filter(|(set1, set2)| set1.is_subset(set2) || set2.is_subset(set1))
-        .count();
-
+    let ans = pairs()?.iter().filter(|pair| pair.fully_contains()).count();
     Ok(ans.to_string())
 }
 
 fn second() -> Result<String> {
-    let data = fs::read_to_string("day4/data/day4.txt")?;
-    let ans: usize = data
-        .lines()
-        .map(|line| make_sets_from_ranges(line).unwrap())
-        .filter(|(set1, set2)| !set1.is_disjoint(set2))
-        .count();
+    let ans = pairs()?.iter().filter(|pair| pair.overlaps()).count();
     Ok(ans.to_string())
 }
 
-#[derive(Debug)]
-struct SectionRange {
-    start: usize,
-    end: usize,
+fn pair_details() -> Result<String> {
+    let mut details = String::new();
+    for pair in pairs()? {
+        let Pair(first, second) = pair;
+        let intersection = pair
+            .intersection()
+            .map_or("none".to_string(), |range| range.to_string());
+        details += &format!(
+            "{first},{second} : overlap {} ({intersection}), union {}\n",
+            pair.overlap_len(),
+            pair.union_len()
+        );
+    }
+    Ok(details)
 }
-impl FromStr for SectionRange {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split('-');
-        let start = parts.next().unwrap().parse()?;
-        let end = parts.next().unwrap().parse()?;
-        Ok(Self { start, end })
+
+fn camp_report() -> Result<String> {
+    let camp = Camp::new(pairs()?);
+    let join = |items: Vec<String>| items.join(" ");
+    let (peak, peak_ranges) = camp.peak_concurrency();
+    let cluster = camp.largest_overlap_cluster();
+    let reassignments = camp.reassignments();
+    let mut report = format!(
+        "Peak concurrency : {peak} elves on {}\n",
+        join(peak_ranges.iter().map(|range| range.to_string()).collect())
+    );
+    report += &format!(
+        "Uncovered sections : {}\n",
+        join(
+            camp.uncovered()
+                .iter()
+                .map(|range| range.to_string())
+                .collect()
+        )
+    );
+    report += &format!(
+        "Largest overlap cluster : {} elves ({})\n",
+        cluster.len(),
+        join(cluster.iter().map(|elf| elf.to_string()).collect())
+    );
+    report += &format!("Reassignments : {}\n", reassignments.len());
+    for reassignment in reassignments {
+        let range = reassignment
+            .range
+            .map_or("nothing".to_string(), |range| range.to_string());
+        report += &format!("  elf {} -> {range}\n", reassignment.elf);
     }
+    Ok(report)
 }
 
-fn make_sets_from_ranges(line: &str) -> Result<(HashSet<usize>, HashSet<usize>)> {
-    let mut ranges = line.split(',');
-    let range1 = SectionRange::from_str(ranges.next().unwrap())?;
-    let range2 = SectionRange::from_str(ranges.next().unwrap())?;
-    let set1: HashSet<usize> = (range1.start..=range1.end).collect();
-    let set2: HashSet<usize> = (range2.start..=range2.end).collect();
-    Ok((set1, set2))
+fn pairs() -> Result<Vec<Pair>> {
+    let data = fs::read_to_string("day4/data/day4.txt")?;
+    data.lines().map(|line| line.parse()).collect()
 }
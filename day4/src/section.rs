@@ -0,0 +1,127 @@
+use anyhow::{anyhow, Error, Result};
+use std::{fmt, str::FromStr};
+
+/// Sections `start` to `end`, both included. `end` is below `usize::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl FromStr for SectionRange {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| anyhow!("Expected a range like 2-4, got {s}"))?;
+        let (start, end) = (start.parse()?, end.parse()?);
+        if start > end {
+            return Err(anyhow!("Range {s} ends before it starts"));
+        }
+        // Keeps `len` and the section after the range within `usize`
+        if end == usize::MAX {
+            return Err(anyhow!("Range {s} ends too far"));
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl fmt::Display for SectionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl SectionRange {
+    pub fn len(&self) -> usize {
+        self.end - self.start + 1
+    }
+
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| Self {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+}
+
+/// Sections assigned to the two elves of a pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair(pub SectionRange, pub SectionRange);
+
+impl FromStr for Pair {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, second) = s
+            .split_once(',')
+            .ok_or_else(|| anyhow!("Expected two ranges, got {s}"))?;
+        Ok(Self(first.parse()?, second.parse()?))
+    }
+}
+
+impl Pair {
+    /// One of the elves has nothing to do that the other does not already do.
+    pub fn fully_contains(&self) -> bool {
+        self.0.contains(&self.1) || self.1.contains(&self.0)
+    }
+
+    pub fn overlaps(&self) -> bool {
+        self.0.overlaps(&self.1)
+    }
+
+    pub fn intersection(&self) -> Option<SectionRange> {
+        self.0.intersection(&self.1)
+    }
+
+    /// Number of sections assigned to both elves.
+    pub fn overlap_len(&self) -> usize {
+        self.intersection().map_or(0, |range| range.len())
+    }
+
+    /// Number of sections assigned to at least one of the elves.
+    pub fn union_len(&self) -> usize {
+        // The overlap is taken out first, the sum of the lengths may not fit in `usize`
+        self.0.len() - self.overlap_len() + self.1.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs() {
+        let pairs: Vec<Pair> = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8"
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        assert_eq!(pairs.iter().filter(|pair| pair.fully_contains()).count(), 2);
+        assert_eq!(pairs.iter().filter(|pair| pair.overlaps()).count(), 4);
+        let overlaps: Vec<usize> = pairs.iter().map(Pair::overlap_len).collect();
+        assert_eq!(overlaps, vec![0, 0, 1, 5, 1, 3]);
+        let unions: Vec<usize> = pairs.iter().map(Pair::union_len).collect();
+        assert_eq!(unions, vec![6, 4, 5, 7, 3, 7]);
+        assert_eq!(pairs[5].intersection(), "4-6".parse().ok());
+
+        // No section is enumerated, so huge ranges cost nothing
+        let pair: Pair = "1-1000000000,999999999-2000000000".parse().unwrap();
+        assert_eq!(pair.overlap_len(), 2);
+        assert_eq!(pair.union_len(), 2000000000);
+        assert!("4-2,1-3".parse::<Pair>().is_err());
+
+        let max = usize::MAX;
+        assert!(format!("1-{max}").parse::<SectionRange>().is_err());
+        let pair: Pair = format!("0-{},1-{}", max - 1, max - 1).parse().unwrap();
+        assert_eq!(pair.0.len(), max);
+        assert_eq!(pair.union_len(), max);
+    }
+}
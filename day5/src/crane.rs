@@ -0,0 +1,249 @@
+use anyhow::{anyhow, Result};
+use std::{error, fmt};
+
+/// Move `nb_crates` crates from stack `from` to stack `to`, stacks being numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub nb_crates: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Stacks of crates, from the bottom to the top.
+pub type Stacks = Vec<Vec<char>>;
+
+/// Why a list of moves cannot be carried out. Stacks are numbered from 1 and moves from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CraneError {
+    UnknownStack {
+        move_index: usize,
+        stack: usize,
+    },
+    NotEnoughCrates {
+        move_index: usize,
+        stack: usize,
+        needed: usize,
+        available: usize,
+    },
+    EmptyStack {
+        stack: usize,
+    },
+}
+
+impl fmt::Display for CraneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CraneError::UnknownStack { move_index, stack } => {
+                write!(f, "Move {move_index} uses stack {stack}, which does not exist")
+            }
+            CraneError::NotEnoughCrates {
+                move_index,
+                stack,
+                needed,
+                available,
+            } => write!(
+                f,
+                "Move {move_index} takes {needed} crates from stack {stack}, which holds {available}"
+            ),
+            CraneError::EmptyStack { stack } => write!(f, "Stack {stack} is empty"),
+        }
+    }
+}
+
+impl error::Error for CraneError {}
+
+/// Checks that move `move_index` can be made, `height` giving the number of crates of a stack
+/// by index.
+fn check_move(
+    move_index: usize,
+    move_: &Move,
+    nb_stacks: usize,
+    height: impl Fn(usize) -> usize,
+) -> Result<(), CraneError> {
+    for stack in [move_.from, move_.to] {
+        if stack == 0 || stack > nb_stacks {
+            return Err(CraneError::UnknownStack { move_index, stack });
+        }
+    }
+    let available = height(move_.from - 1);
+    if available < move_.nb_crates {
+        return Err(CraneError::NotEnoughCrates {
+            move_index,
+            stack: move_.from,
+            needed: move_.nb_crates,
+            available,
+        });
+    }
+    Ok(())
+}
+
+/// Checks a whole list of moves against the initial stacks without moving any crate. Every crane
+/// moves the same number of crates, so the result holds for all of them.
+pub fn dry_run(stacks: &Stacks, moves: &[Move]) -> Result<(), CraneError> {
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    for (move_index, move_) in moves.iter().enumerate() {
+        check_move(move_index, move_, heights.len(), |stack| heights[stack])?;
+        heights[move_.from - 1] -= move_.nb_crates;
+        heights[move_.to - 1] += move_.nb_crates;
+    }
+    Ok(())
+}
+
+/// Top crate of every stack.
+pub fn top_crates(stacks: &Stacks) -> Result<String, CraneError> {
+    stacks
+        .iter()
+        .enumerate()
+        .map(|(i, stack)| stack.last().ok_or(CraneError::EmptyStack { stack: i + 1 }))
+        .collect()
+}
+
+pub trait Crane {
+    /// Moves crates between two different stacks, given by index.
+    fn move_crates(&self, stacks: &mut Stacks, nb_crates: usize, from: usize, to: usize);
+
+    /// Stops at the first move that cannot be made, leaving the stacks as they were before it.
+    fn apply(&self, stacks: &mut Stacks, moves: &[Move]) -> Result<(), CraneError> {
+        for (move_index, move_) in moves.iter().enumerate() {
+            check_move(move_index, move_, stacks.len(), |stack| stacks[stack].len())?;
+            if move_.from != move_.to {
+                self.move_crates(stacks, move_.nb_crates, move_.from - 1, move_.to - 1);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lifts one crate at a time, reversing the order of the crates it moves.
+pub struct CrateMover9000;
+
+/// Lifts all the crates at once, keeping their order.
+pub struct CrateMover9001;
+
+/// Lifts at most `capacity` crates at once, so large moves are split into several lifts.
+pub struct CapacityLimited {
+    pub capacity: usize,
+}
+
+/// Pulls the crates from the bottom of the stack and puts them on top of the other stack,
+/// keeping their order.
+pub struct BottomTaking;
+
+impl Crane for CrateMover9000 {
+    fn move_crates(&self, stacks: &mut Stacks, nb_crates: usize, from: usize, to: usize) {
+        for _ in 0..nb_crates {
+            let crate_ = stacks[from].pop().unwrap();
+            stacks[to].push(crate_);
+        }
+    }
+}
+
+impl Crane for CrateMover9001 {
+    fn move_crates(&self, stacks: &mut Stacks, nb_crates: usize, from: usize, to: usize) {
+        let split = stacks[from].len() - nb_crates;
+        let mut crates = stacks[from].split_off(split);
+        stacks[to].append(&mut crates);
+    }
+}
+
+impl Crane for CapacityLimited {
+    fn move_crates(&self, stacks: &mut Stacks, nb_crates: usize, from: usize, to: usize) {
+        let mut nb_left = nb_crates;
+        while nb_left > 0 {
+            let lift = nb_left.min(self.capacity);
+            CrateMover9001.move_crates(stacks, lift, from, to);
+            nb_left -= lift;
+        }
+    }
+}
+
+impl Crane for BottomTaking {
+    fn move_crates(&self, stacks: &mut Stacks, nb_crates: usize, from: usize, to: usize) {
+        let crates: Vec<char> = stacks[from].drain(..nb_crates).collect();
+        stacks[to].extend(crates);
+    }
+}
+
+/// `9000`, `9001`, `bottom` or `limited:<capacity>`.
+pub fn crane_from_name(name: &str) -> Result<Box<dyn Crane>> {
+    match name {
+        "9000" => Ok(Box::new(CrateMover9000)),
+        "9001" => Ok(Box::new(CrateMover9001)),
+        "bottom" => Ok(Box::new(BottomTaking)),
+        _ => {
+            let capacity: usize = name
+                .strip_prefix("limited:")
+                .ok_or_else(|| anyhow!("Unknown crane {name}"))?
+                .parse()?;
+            if capacity == 0 {
+                return Err(anyhow!("A crane must lift at least one crate"));
+            }
+            Ok(Box::new(CapacityLimited { capacity }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_example(crane: &dyn Crane) -> String {
+        let mut stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+        let moves =
+            [(1, 2, 1), (3, 1, 3), (2, 2, 1), (1, 1, 2)].map(|(nb_crates, from, to)| Move {
+                nb_crates,
+                from,
+                to,
+            });
+        crane.apply(&mut stacks, &moves).unwrap();
+        top_crates(&stacks).unwrap()
+    }
+
+    #[test]
+    fn cranes() {
+        assert_eq!(run_example(&CrateMover9000), "CMZ");
+        assert_eq!(run_example(&CrateMover9001), "MCD");
+        assert_eq!(run_example(&CapacityLimited { capacity: 1 }), "CMZ");
+        assert_eq!(run_example(&CapacityLimited { capacity: 2 }), "MCZ");
+        assert_eq!(run_example(&BottomTaking), "DCM");
+        assert!(crane_from_name("limited:0").is_err());
+        assert!(crane_from_name("9002").is_err());
+    }
+
+    #[test]
+    fn checked_moves() {
+        let mut stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+        let moves = [(1, 2, 1), (4, 1, 3), (2, 2, 4)].map(|(nb_crates, from, to)| Move {
+            nb_crates,
+            from,
+            to,
+        });
+        let not_enough = CraneError::NotEnoughCrates {
+            move_index: 1,
+            stack: 1,
+            needed: 4,
+            available: 3,
+        };
+        assert_eq!(dry_run(&stacks, &moves), Err(not_enough.clone()));
+        assert_eq!(
+            not_enough.to_string(),
+            "Move 1 takes 4 crates from stack 1, which holds 3"
+        );
+        assert_eq!(
+            dry_run(&stacks, &[moves[2]]),
+            Err(CraneError::UnknownStack {
+                move_index: 0,
+                stack: 4
+            })
+        );
+
+        assert_eq!(CrateMover9000.apply(&mut stacks, &moves), Err(not_enough));
+        assert_eq!(stacks, vec![vec!['Z', 'N', 'D'], vec!['M', 'C'], vec!['P']]);
+        CrateMover9001.apply(&mut stacks, &[moves[0]]).unwrap();
+        CrateMover9001.apply(&mut stacks, &[moves[0]]).unwrap();
+        assert_eq!(
+            top_crates(&stacks),
+            Err(CraneError::EmptyStack { stack: 2 })
+        );
+    }
+}
@@ -1,30 +1,51 @@
-use anyhow::Result;
-use itertools::Itertools;
+mod crane;
+
+use anyhow::{anyhow, Result};
+use crane::{
+    crane_from_name, dry_run, top_crates, Crane, CrateMover9000, CrateMover9001, Move, Stacks,
+};
 use std::fs;
 
 fn main() -> Result<()> {
+    // `cargo run -p day5 -- --check` validates the moves without running any crane
+    if std::env::args().any(|arg| arg == "--check") {
+        let (stacks, moves) = stacks_and_moves()?;
+        dry_run(&stacks, &moves)?;
+        println!("The {} moves can all be made", moves.len());
+        return Ok(());
+    }
     println!("First part : {}", first()?);
     println!("Second part : {}", second()?);
+    // `cargo run -p day5 -- --crane limited:3` runs another crane: `9000`, `9001`, `bottom` or
+    // `limited:<capacity>`
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(i) = args.iter().position(|arg| arg == "--crane") {
+        let name = args
+            .get(i + 1)
+            .ok_or_else(|| anyhow!("--crane expects a crane name"))?;
+        println!(
+            "Crane {name} : {}",
+            top_crates_with(&*crane_from_name(name)?)?
+        );
+    }
     Ok(())
 }
 
 fn first() -> Result<String> {
-    let data = fs::read_to_string("day5/data/day5.txt")?;
-    let mut lines = data.lines();
-    let lines = lines.by_ref();
-
-    let lines_for_stacks: Vec<&str> = lines.take_while(|line| !line.is_empty()).collect();
-    let stacks = create_stacks(&lines_for_stacks);
+    top_crates_with(&CrateMover9000)
+}
 
-    let lines_for_moves: Vec<&str> = lines.collect();
-    let moves = create_moves(&lines_for_moves);
+fn second() -> Result<String> {
+    top_crates_with(&CrateMover9001)
+}
 
-    let final_stacks = apply_stack_moves(stacks, moves);
-    let ans = get_top_crates(final_stacks);
-    Ok(ans)
+fn top_crates_with(crane: &dyn Crane) -> Result<String> {
+    let (mut stacks, moves) = stacks_and_moves()?;
+    crane.apply(&mut stacks, &moves)?;
+    Ok(top_crates(&stacks)?)
 }
 
-fn second() -> Result<String> {
+fn stacks_and_moves() -> Result<(Stacks, Vec<Move>)> {
     let data = fs::read_to_string("day5/data/day5.txt")?;
     let mut lines = data.lines();
     let lines = lines.by_ref();
@@ -34,19 +55,10 @@ fn second() -> Result<String> {
 
     let lines_for_moves: Vec<&str> = lines.collect();
     let moves = create_moves(&lines_for_moves);
-
-    let final_stacks = apply_stack_moves_9001(stacks, moves);
-    let ans = get_top_crates(final_stacks);
-    Ok(ans)
-}
-#[derive(Debug)]
-struct Move {
-    nb_to_pop: usize,
-    stack_to_pop: usize,
-    stack_to_push: usize,
+    Ok((stacks, moves))
 }
 
-fn create_stacks(lines: &[&str]) -> Vec<Vec<char>> {
+fn create_stacks(lines: &[&str]) -> Stacks {
     let line_with_num = lines.last().unwrap();
     let nb_stacks = line_with_num
         .chars()
@@ -72,15 +84,15 @@ fn create_moves(lines: &[&str]) -> Vec<Move> {
             let mut line = line.chars();
             let line = line.by_ref();
             let _ = line.take(5).last(); // remove "move "
-            let nb_to_pop = get_number(line).unwrap();
+            let nb_crates = get_number(line).unwrap();
             let _ = line.take(5).last(); // remove "from "
-            let stack_to_pop = get_number(line).unwrap();
+            let from = get_number(line).unwrap();
             let _ = line.take(3).last(); // remove "to "
-            let stack_to_push = get_number(line).unwrap();
+            let to = get_number(line).unwrap();
             Move {
-                nb_to_pop,
-                stack_to_pop,
-                stack_to_push,
+                nb_crates,
+                from,
+                to,
             }
         })
         .collect()
@@ -90,40 +102,3 @@ fn get_number(line: &mut impl Iterator<Item = char>) -> Result<usize> {
     let n: String = line.take_while(|c| !c.is_whitespace()).collect();
     Ok(n.parse::<usize>()?)
 }
-
-fn apply_stack_moves(mut stacks: Vec<Vec<char>>, moves: Vec<Move>) -> Vec<Vec<char>> {
-    moves.iter().for_each(
-        |Move {
-             nb_to_pop,
-             stack_to_pop,
-             stack_to_push,
-         }| {
-            for _ in 0..*nb_to_pop {
-                let crate_ = stacks[*stack_to_pop - 1].pop().unwrap();
-                stacks[*stack_to_push - 1].push(crate_);
-            }
-        },
-    );
-    stacks
-}
-
-fn apply_stack_moves_9001(mut stacks: Vec<Vec<char>>, moves: Vec<Move>) -> Vec<Vec<char>> {
-    moves.iter().for_each(
-        |Move {
-             nb_to_pop,
-             stack_to_pop,
-             stack_to_push,
-         }| {
-            let mut crates = (0..*nb_to_pop)
-                .map(|_| stacks[*stack_to_pop - 1].pop().unwrap())
-                .collect_vec();
-            crates.reverse();
-            stacks[*stack_to_push - 1].append(&mut crates);
-        },
-    );
-    stacks
-}
-
-fn get_top_crates(stacks: Vec<Vec<char>>) -> String {
-    stacks.iter().map(|stack| stack.last().unwrap()).collect()
-}
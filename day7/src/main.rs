@@ -1,6 +1,10 @@
 use anyhow::{anyhow, Error, Result};
-use itertools::Itertools;
-use std::{collections::HashSet, fs, str::FromStr};
+use aoc_utils::graph::{
+    dgraph::{Dgraph, Edge},
+    traversal::Traversal,
+    Graph,
+};
+use std::{collections::HashMap, fs, str::FromStr};
 
 fn main() -> Result<()> {
     println!("First part : {}", first()?);
@@ -10,10 +14,12 @@ fn main() -> Result<()> {
 
 fn first() -> Result<String> {
     let data = fs::read_to_string("day7/data/day7.txt")?;
-    let file_and_paths = get_all_paths(&data)?;
-    let directories: HashSet<&String> = file_and_paths.iter().map(|(_, path)| path).collect();
-    let directories_size = calculate_size_of_directories(&directories, &file_and_paths);
-    let ans: usize = directories_size.filter(|&size| size <= 100000).sum();
+    let file_system = FileSystem::from_str(&data)?;
+    let ans: usize = file_system
+        .directory_sizes()
+        .into_iter()
+        .filter(|&size| size <= 100000)
+        .sum();
 
     Ok(ans.to_string())
 }
@@ -23,45 +29,87 @@ fn second() -> Result<String> {
     const REQUIRED_SPACE: usize = 30000000;
 
     let data = fs::read_to_string("day7/data/day7.txt")?;
-    let file_and_paths = get_all_paths(&data)?;
-    let directories: HashSet<&String> = file_and_paths.iter().map(|(_, path)| path).collect();
-    let directories_size = calculate_size_of_directories(&directories, &file_and_paths)
-        .sorted()
-        .collect_vec();
+    let file_system = FileSystem::from_str(&data)?;
+    let directories_size = file_system.directory_sizes();
 
-    let space_left = AVAILABLE_SPACE - directories_size.iter().max().unwrap();
+    let space_left = AVAILABLE_SPACE - directories_size[FileSystem::ROOT];
     let space_needed = REQUIRED_SPACE - space_left;
 
     // Find the directory with the size bigger than `space_needed` and the closest to `space_needed`
-    let (ans, _) = directories_size
+    let ans = directories_size
         .into_iter()
         .filter(|&size| size > space_needed)
-        .map(|size| (size, size - space_needed))
-        .min_by_key(|x| x.1)
+        .min()
         .unwrap();
     Ok(ans.to_string())
 }
 
-fn get_all_paths(data: &str) -> Result<Vec<(usize, String)>> {
-    let mut pwd = Vec::new();
-    let mut file_and_paths = Vec::new();
-    for line in data.lines() {
-        match Entry::from_str(line)? {
-            Entry::Command(Command::Cd(dir)) => {
-                apply_cd(&dir, &mut pwd);
-            }
-            Entry::Dir => {
-                let path = pwd.join("/").replace("//", "/");
-                file_and_paths.push((0, path))
+/// Directory tree: vertex `ROOT` is `/` and edges go from a directory to its subdirectories.
+struct FileSystem {
+    tree: Dgraph,
+    /// Size of the files directly inside each directory
+    file_sizes: Vec<usize>,
+}
+
+impl FileSystem {
+    const ROOT: usize = 0;
+
+    /// Total size of each directory, subdirectories included.
+    fn directory_sizes(&self) -> Vec<usize> {
+        let mut sizes = self.file_sizes.clone();
+        // Subdirectories are finished before their parent
+        for directory in self.tree.dfs_postorder(Self::ROOT) {
+            for subdirectory in self.tree.neighbors(directory) {
+                sizes[directory] += sizes[subdirectory.dst];
             }
-            Entry::File(file_size) => {
-                let path = pwd.join("/").replace("//", "/");
-                file_and_paths.push((file_size, path))
+        }
+        sizes
+    }
+}
+
+impl FromStr for FileSystem {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tree = Dgraph::new(1);
+        let mut file_sizes = vec![0];
+        let mut ids = HashMap::from([("/".to_string(), Self::ROOT)]);
+        let mut pwd = Vec::new();
+        let mut pwd_ids = Vec::new();
+        for line in s.lines() {
+            match Entry::from_str(line)? {
+                Entry::Command(Command::Cd(dir)) => {
+                    apply_cd(&dir, &mut pwd);
+                    if dir == ".." {
+                        pwd_ids.pop();
+                        continue;
+                    }
+                    let path = pwd.join("/").replace("//", "/");
+                    let id = *ids.entry(path).or_insert_with(|| {
+                        let id = tree.nb_vertices();
+                        tree.add_vertex();
+                        file_sizes.push(0);
+                        if let Some(&parent) = pwd_ids.last() {
+                            tree.add_edge(Edge {
+                                src: parent,
+                                dst: id,
+                                weight: 1,
+                            });
+                        }
+                        id
+                    });
+                    pwd_ids.push(id);
+                }
+                Entry::File(file_size) => {
+                    let current_dir = *pwd_ids
+                        .last()
+                        .ok_or_else(|| anyhow!("File listed outside of a directory"))?;
+                    file_sizes[current_dir] += file_size;
+                }
+                _ => (),
             }
-            _ => (),
         }
+        Ok(Self { tree, file_sizes })
     }
-    Ok(file_and_paths)
 }
 
 fn apply_cd(next_dir: &str, tree_path: &mut Vec<String>) {
@@ -72,19 +120,6 @@ fn apply_cd(next_dir: &str, tree_path: &mut Vec<String>) {
     }
 }
 
-fn calculate_size_of_directories<'a>(
-    directories: &'a HashSet<&'a String>,
-    all_files: &'a [(usize, String)],
-) -> impl Iterator<Item = usize> + 'a {
-    directories.iter().map(|&directory| {
-        let files_of_directory = all_files
-            .iter()
-            .filter(|(_, file)| file.starts_with(directory));
-        let total_size: usize = files_of_directory.map(|(file_size, _)| file_size).sum();
-        total_size
-    })
-}
-
 enum Command {
     Cd(String),
     Ls,